@@ -0,0 +1,30 @@
+use crate::{floor::Floor, tag::ElementTag};
+
+// What a screen reader announces for a slot: everything its id says, most
+// telling first, ending with the floor it sits on.
+pub fn slot_name(element_tag: &ElementTag, floor: &Floor) -> String {
+    let mut parts = vec![format!("Slot {}", element_tag.slot.clone().unwrap_or_default())];
+    if let Some(kind) = &element_tag.kind {
+        parts.push(kind.clone());
+    }
+    if let Some(zone) = &element_tag.zone {
+        parts.push(format!("zone {}", zone));
+    }
+    if let Some(building) = &element_tag.building {
+        parts.push(format!("building {}", building));
+    }
+    if !element_tag.tags.is_empty() {
+        parts.push(format!("tagged {}", element_tag.tags.join(" ")));
+    }
+    parts.push(floor.label());
+    parts.join(", ")
+}
+
+// Name of the plan as a whole, e.g. "Floor 2 plan, 3 slots".
+pub fn plan_name(floor: &Floor, slots: usize) -> String {
+    let count = match slots {
+        1 => "1 slot".to_string(),
+        slots => format!("{} slots", slots),
+    };
+    format!("{} plan, {}", floor.label(), count)
+}
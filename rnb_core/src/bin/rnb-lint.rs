@@ -0,0 +1,64 @@
+// Validates floor-plan SVGs before upload.
+//
+//     rnb-lint [--json] <plan.svg>...
+//
+// Exits with status 1 when any file has issues, so it can run as a pre-commit hook.
+
+use std::{env, fs, process::ExitCode};
+
+use rnb_core::{lint::{lint, Issue}, tag::GRAMMAR_VERSION};
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+struct Report {
+    file: String,
+    grammar_version: u32,
+    issues: Vec<Issue>,
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut files = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("usage: rnb-lint [--json] <plan.svg>...");
+                return ExitCode::SUCCESS;
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        eprintln!("usage: rnb-lint [--json] <plan.svg>...");
+        return ExitCode::from(2);
+    }
+
+    let mut reports = Vec::new();
+    for file in files {
+        let issues = match fs::read_to_string(&file) {
+            Ok(content) => lint(&content),
+            Err(e) => vec![Issue { rule: "unreadable-file", id: None, message: e.to_string() }],
+        };
+        reports.push(Report { file, grammar_version: GRAMMAR_VERSION, issues });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports).expect("Failed to serialize report"));
+    } else {
+        for report in &reports {
+            for issue in &report.issues {
+                match &issue.id {
+                    Some(id) => println!("{}: [{}] {}: {}", report.file, issue.rule, id, issue.message),
+                    None => println!("{}: [{}] {}", report.file, issue.rule, issue.message),
+                }
+            }
+        }
+    }
+
+    if reports.iter().any(|report| !report.issues.is_empty()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{floor::FloorOverride, theme::ThemeOverride};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntityResponse {
+    pub name: String,
+    pub svg_raw_content: Option<String>,
+    pub svg_content: Option<String>,
+    pub default_floor: String,
+    #[serde(default)]
+    pub theme: Option<ThemeOverride>,
+    // drawing units per metre, for real-world areas and lengths
+    #[serde(default)]
+    pub units_per_metre: Option<f64>,
+    // names, levels and aliases for the drawing's floors
+    #[serde(default)]
+    pub floors: Vec<FloorOverride>,
+}
@@ -0,0 +1,87 @@
+use serde::{Serialize, Deserialize};
+
+// A storey of the drawing, known by its `floor-*` token.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Floor {
+    pub token: String,
+    // storeys above ground count up from 0, basements down from -1
+    pub level: f64,
+    pub name: String,
+    // metres above the ground floor
+    pub elevation: Option<f64>,
+    // short form such as "B1" or "M"
+    pub alias: Option<String>,
+}
+impl Floor {
+    // What the token itself says: `floor-2` is level 2, `floor-b1` the first
+    // basement, `floor-g` the ground floor. Anything else sits at level 0 under
+    // its own name.
+    pub fn from_token(token: &str) -> Self {
+        let value = token.strip_prefix("floor-").unwrap_or(token);
+        let lower = value.to_ascii_lowercase();
+        let basement = lower.strip_prefix('b').and_then(|depth| depth.parse::<f64>().ok());
+        let number = value.parse::<f64>().ok();
+        let (level, name, alias) = match (number, basement) {
+            _ if lower == "g" || number == Some(0.0) => (0.0, "Ground floor".to_string(), Some("G".to_string())),
+            (Some(level), _) => (level, format!("Floor {}", value), None),
+            (_, Some(depth)) => (-depth, format!("Basement {}", depth), Some(value.to_ascii_uppercase())),
+            _ => (0.0, value.to_string(), None),
+        };
+        Self { token: token.to_string(), level, name, elevation: None, alias }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.alias {
+            Some(alias) => format!("{} ({})", self.name, alias),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn with_override(&self, floor_override: &FloorOverride) -> Self {
+        Self {
+            token: self.token.clone(),
+            level: floor_override.level.unwrap_or(self.level),
+            name: floor_override.name.clone().unwrap_or(self.name.clone()),
+            elevation: floor_override.elevation.or(self.elevation),
+            alias: floor_override.alias.clone().or(self.alias.clone()),
+        }
+    }
+}
+
+// Per-entity floor metadata; unset fields keep what the token says.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FloorOverride {
+    pub token: String,
+    pub level: Option<f64>,
+    pub name: Option<String>,
+    pub elevation: Option<f64>,
+    pub alias: Option<String>,
+}
+
+// The floors the drawing has, bottom first, with `overrides` applied.
+pub fn ordered(tokens: &[String], overrides: &[FloorOverride]) -> Vec<Floor> {
+    let mut floors: Vec<Floor> = tokens
+        .iter()
+        .map(|token| {
+            let floor = Floor::from_token(token);
+            match overrides.iter().find(|floor_override| floor_override.token == *token) {
+                Some(floor_override) => floor.with_override(floor_override),
+                None => floor,
+            }
+        })
+        .collect();
+    floors.sort_by(|a, b| {
+        a.level
+            .total_cmp(&b.level)
+            .then(a.elevation.unwrap_or(0.0).total_cmp(&b.elevation.unwrap_or(0.0)))
+            .then(a.token.cmp(&b.token))
+    });
+    floors
+}
+
+// The floor `offset` storeys from `current` in `floors`, if there is one.
+pub fn step<'a>(floors: &'a [Floor], current: &str, offset: isize) -> Option<&'a Floor> {
+    let index = floors.iter().position(|floor| floor.token == current)?;
+    floors.get(index.checked_add_signed(offset)?)
+}
@@ -0,0 +1,601 @@
+use std::{collections::{HashMap, HashSet}, f64::consts::PI};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    liquid::{Element, Node, Svg, Tag},
+    tag::ElementTag,
+    viewport::ViewBox,
+};
+
+// Segments used to flatten circles, ellipses, arcs and curves.
+const CURVE_STEPS: usize = 64;
+
+// SVG's `matrix(a b c d e f)`: x' = a*x + c*y + e, y' = b*x + d*y + f.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+impl Transform {
+    pub const IDENTITY: Self = Self { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub fn translate(x: f64, y: f64) -> Self {
+        Self { e: x, f: y, ..Self::IDENTITY }
+    }
+
+    // Clockwise on screen, about the origin.
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self { a: cos, b: sin, c: -sin, d: cos, ..Self::IDENTITY }
+    }
+
+    // A `transform` attribute. Functions it does not know are skipped.
+    pub fn parse(value: &str) -> Self {
+        value
+            .split(')')
+            .filter_map(|function| {
+                let (name, arguments) = function.split_once('(')?;
+                let name = name.trim().trim_start_matches(',').trim();
+                let arguments = numbers(arguments);
+                let argument = |index: usize, default: f64| arguments.get(index).copied().unwrap_or(default);
+                match name {
+                    "matrix" if arguments.len() == 6 => Some(Self {
+                        a: arguments[0],
+                        b: arguments[1],
+                        c: arguments[2],
+                        d: arguments[3],
+                        e: arguments[4],
+                        f: arguments[5],
+                    }),
+                    "translate" => Some(Self::translate(argument(0, 0.0), argument(1, 0.0))),
+                    "scale" => {
+                        let x = argument(0, 1.0);
+                        Some(Self { a: x, d: argument(1, x), ..Self::IDENTITY })
+                    }
+                    "rotate" => {
+                        let (x, y) = (argument(1, 0.0), argument(2, 0.0));
+                        Some(Self::translate(x, y).then(&Self::rotate(argument(0, 0.0))).then(&Self::translate(-x, -y)))
+                    }
+                    "skewX" => Some(Self { c: argument(0, 0.0).to_radians().tan(), ..Self::IDENTITY }),
+                    "skewY" => Some(Self { b: argument(0, 0.0).to_radians().tan(), ..Self::IDENTITY }),
+                    _ => None,
+                }
+            })
+            .fold(Self::IDENTITY, |transform, next| transform.then(&next))
+    }
+
+    // `self` applied after `inner`, the way a parent's transform wraps its
+    // child's.
+    pub fn then(&self, inner: &Self) -> Self {
+        Self {
+            a: self.a * inner.a + self.c * inner.b,
+            b: self.b * inner.a + self.d * inner.b,
+            c: self.a * inner.c + self.c * inner.d,
+            d: self.b * inner.c + self.d * inner.d,
+            e: self.a * inner.e + self.c * inner.f + self.e,
+            f: self.b * inner.e + self.d * inner.f + self.f,
+        }
+    }
+
+    pub fn apply(&self, point: (f64, f64)) -> (f64, f64) {
+        let (x, y) = point;
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+}
+
+// Area, perimeter and centroid of everything drawn for one slot.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Measure {
+    // square drawing units, or square metres once scaled
+    pub area: f64,
+    pub perimeter: f64,
+    // always in drawing coordinates, so it can be drawn on the plan
+    pub centroid: Option<(f64, f64)>,
+}
+impl Measure {
+    pub fn scaled(&self, units_per_metre: f64) -> Self {
+        Self {
+            area: self.area / (units_per_metre * units_per_metre),
+            perimeter: self.perimeter / units_per_metre,
+            centroid: self.centroid,
+        }
+    }
+}
+
+// One flattened subpath in drawing coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ring {
+    pub points: Vec<(f64, f64)>,
+    pub closed: bool,
+}
+impl Ring {
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        Self { points: self.points.iter().map(|point| transform.apply(*point)).collect(), closed: self.closed }
+    }
+
+    // The ring as a `points` attribute value.
+    pub fn points_value(&self) -> String {
+        self.points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        let closing = match (self.closed, self.points.first(), self.points.last()) {
+            (true, Some(first), Some(last)) => distance(*last, *first),
+            _ => 0.0,
+        };
+        self.points.windows(2).map(|pair| distance(pair[0], pair[1])).sum::<f64>() + closing
+    }
+
+    // Shoelace area, signed by winding. Open rings enclose nothing.
+    fn signed_area(&self) -> f64 {
+        if !self.closed {
+            return 0.0;
+        }
+        self.edges().map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1).sum::<f64>() / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    // Centroid of the enclosed region, or of the vertices for open or
+    // degenerate rings.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let signed_area = self.signed_area();
+        if signed_area.abs() < f64::EPSILON {
+            let count = self.points.len() as f64;
+            let (x, y) = self.points.iter().fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+            return Some((x / count, y / count));
+        }
+        let (x, y) = self.edges().fold((0.0, 0.0), |(x, y), ((x1, y1), (x2, y2))| {
+            let cross = x1 * y2 - x2 * y1;
+            (x + (x1 + x2) * cross, y + (y1 + y2) * cross)
+        });
+        Some((x / (6.0 * signed_area), y / (6.0 * signed_area)))
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        self.points.iter().copied().zip(self.points.iter().copied().cycle().skip(1))
+    }
+}
+
+fn distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.0 - from.0).hypot(to.1 - from.1)
+}
+
+fn numbers(value: &str) -> Vec<f64> {
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|number| !number.is_empty())
+        .filter_map(|number| number.parse::<f64>().ok())
+        .collect()
+}
+
+fn length(element: &Element, name: &str) -> f64 {
+    element
+        .property(name)
+        .and_then(|value| value.trim_end_matches("px").trim().parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+fn ellipse(cx: f64, cy: f64, rx: f64, ry: f64) -> Vec<(f64, f64)> {
+    (0..CURVE_STEPS)
+        .map(|step| {
+            let angle = 2.0 * PI * step as f64 / CURVE_STEPS as f64;
+            (cx + rx * angle.cos(), cy + ry * angle.sin())
+        })
+        .collect()
+}
+
+// Rings of one shape in its own coordinates; `<g>` and `<use>` are handled by
+// the walk.
+fn shape_rings(element: &Element) -> Vec<Ring> {
+    let points = |name: &str| -> Vec<(f64, f64)> {
+        numbers(element.property(name).unwrap_or(""))
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    };
+    match element.tag {
+        Tag::Polygon => vec![Ring { points: points("points"), closed: true }],
+        Tag::Polyline => vec![Ring { points: points("points"), closed: false }],
+        Tag::Rect => {
+            let (x, y) = (length(element, "x"), length(element, "y"));
+            let (width, height) = (length(element, "width"), length(element, "height"));
+            vec![Ring { points: vec![(x, y), (x + width, y), (x + width, y + height), (x, y + height)], closed: true }]
+        }
+        Tag::Circle => {
+            let r = length(element, "r");
+            vec![Ring { points: ellipse(length(element, "cx"), length(element, "cy"), r, r), closed: true }]
+        }
+        Tag::Ellipse => {
+            let (rx, ry) = (length(element, "rx"), length(element, "ry"));
+            vec![Ring { points: ellipse(length(element, "cx"), length(element, "cy"), rx, ry), closed: true }]
+        }
+        Tag::Line => vec![Ring {
+            points: vec![(length(element, "x1"), length(element, "y1")), (length(element, "x2"), length(element, "y2"))],
+            closed: false,
+        }],
+        Tag::Path => path_rings(element.property("d").unwrap_or("")),
+        _ => Vec::new(),
+    }
+}
+
+enum PathToken {
+    Command(char),
+    Number(f64),
+}
+
+fn path_tokens(d: &str) -> Vec<PathToken> {
+    let mut tokens = Vec::new();
+    let mut chars = d.char_indices().peekable();
+    // numbers read since the last command, to find the arc flags
+    let mut command = ' ';
+    let mut argument = 0;
+    while let Some((start, c)) = chars.next() {
+        if c.is_ascii_alphabetic() && !matches!(c, 'e' | 'E') {
+            tokens.push(PathToken::Command(c));
+            command = c;
+            argument = 0;
+            continue;
+        }
+        if !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.')) {
+            continue;
+        }
+        // the large-arc and sweep flags are one character each, so minified
+        // paths write `a5 5 0 1110 0` for flags 1 1 and end point 10 0
+        if command.eq_ignore_ascii_case(&'a') && matches!(argument % 7, 3 | 4) && matches!(c, '0' | '1') {
+            tokens.push(PathToken::Number(if c == '1' { 1.0 } else { 0.0 }));
+            argument += 1;
+            continue;
+        }
+        // a second `.` or a sign not after an exponent starts the next number
+        let mut end = start + c.len_utf8();
+        let mut seen_dot = c == '.';
+        let mut previous = c;
+        while let Some(&(index, next)) = chars.peek() {
+            let continues = next.is_ascii_digit()
+                || (next == '.' && !seen_dot)
+                || matches!(next, 'e' | 'E')
+                || (matches!(next, '-' | '+') && matches!(previous, 'e' | 'E'));
+            if !continues {
+                break;
+            }
+            seen_dot |= next == '.';
+            previous = next;
+            end = index + next.len_utf8();
+            chars.next();
+        }
+        if let Ok(number) = d[start..end].parse::<f64>() {
+            tokens.push(PathToken::Number(number));
+            argument += 1;
+        }
+    }
+    tokens
+}
+
+fn cubic(from: (f64, f64), control_1: (f64, f64), control_2: (f64, f64), to: (f64, f64)) -> Vec<(f64, f64)> {
+    (1..=CURVE_STEPS)
+        .map(|step| {
+            let t = step as f64 / CURVE_STEPS as f64;
+            let u = 1.0 - t;
+            let weights = [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t];
+            let points = [from, control_1, control_2, to];
+            points
+                .iter()
+                .zip(weights)
+                .fold((0.0, 0.0), |(x, y), (point, weight)| (x + point.0 * weight, y + point.1 * weight))
+        })
+        .collect()
+}
+
+fn quadratic(from: (f64, f64), control: (f64, f64), to: (f64, f64)) -> Vec<(f64, f64)> {
+    let toward_control = |end: (f64, f64)| (end.0 + (control.0 - end.0) * 2.0 / 3.0, end.1 + (control.1 - end.1) * 2.0 / 3.0);
+    cubic(from, toward_control(from), toward_control(to), to)
+}
+
+// Endpoint arc parameterisation converted to centre form, per the SVG
+// implementation notes (F.6.5).
+fn arc(from: (f64, f64), radii: (f64, f64), rotation: f64, large_arc: bool, sweep: bool, to: (f64, f64)) -> Vec<(f64, f64)> {
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+    if rx == 0.0 || ry == 0.0 || from == to {
+        return vec![to];
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+    let (cx, cy) = (cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0, sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0);
+    let angle = |ux: f64, uy: f64| uy.atan2(ux);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+    (1..=CURVE_STEPS)
+        .map(|step| {
+            let theta = start + delta * step as f64 / CURVE_STEPS as f64;
+            let (x, y) = (rx * theta.cos(), ry * theta.sin());
+            (cos * x - sin * y + cx, sin * x + cos * y + cy)
+        })
+        .collect()
+}
+
+// Flattens a path's `d` into one ring per subpath.
+fn path_rings(d: &str) -> Vec<Ring> {
+    let tokens = path_tokens(d);
+    let mut rings = Vec::new();
+    let mut ring = Ring { points: Vec::new(), closed: false };
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // reflected control point for S and T
+    let mut last_control: Option<(char, (f64, f64))> = None;
+    let mut command = ' ';
+    let mut index = 0;
+    let finish = |ring: &mut Ring, rings: &mut Vec<Ring>| {
+        if ring.points.len() > 1 {
+            rings.push(std::mem::replace(ring, Ring { points: Vec::new(), closed: false }));
+        } else {
+            ring.points.clear();
+        }
+    };
+    while index < tokens.len() {
+        if let PathToken::Command(next) = tokens[index] {
+            command = next;
+            index += 1;
+            if command.eq_ignore_ascii_case(&'z') {
+                ring.closed = true;
+                finish(&mut ring, &mut rings);
+                current = start;
+                last_control = None;
+                continue;
+            }
+        }
+        let arity = match command.to_ascii_lowercase() {
+            'm' | 'l' | 't' => 2,
+            'h' | 'v' => 1,
+            's' | 'q' => 4,
+            'c' => 6,
+            'a' => 7,
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        let arguments: Vec<f64> = tokens[index..]
+            .iter()
+            .take(arity)
+            .map_while(|token| match token {
+                PathToken::Number(number) => Some(*number),
+                PathToken::Command(_) => None,
+            })
+            .collect();
+        if arguments.len() < arity {
+            index += arguments.len().max(1);
+            continue;
+        }
+        index += arity;
+        let relative = command.is_ascii_lowercase();
+        let point = |x: f64, y: f64| if relative { (current.0 + x, current.1 + y) } else { (x, y) };
+        let reflected = |kinds: [char; 2]| match last_control {
+            Some((kind, control)) if kinds.contains(&kind) => (2.0 * current.0 - control.0, 2.0 * current.1 - control.1),
+            _ => current,
+        };
+        let (points, control) = match command.to_ascii_lowercase() {
+            'm' => {
+                finish(&mut ring, &mut rings);
+                let to = point(arguments[0], arguments[1]);
+                ring.points.push(to);
+                current = to;
+                start = to;
+                last_control = None;
+                // further pairs after a moveto are linetos
+                command = if relative { 'l' } else { 'L' };
+                continue;
+            }
+            'l' => (vec![point(arguments[0], arguments[1])], None),
+            'h' => (vec![(if relative { current.0 + arguments[0] } else { arguments[0] }, current.1)], None),
+            'v' => (vec![(current.0, if relative { current.1 + arguments[0] } else { arguments[0] })], None),
+            'c' => {
+                let control_2 = point(arguments[2], arguments[3]);
+                let to = point(arguments[4], arguments[5]);
+                (cubic(current, point(arguments[0], arguments[1]), control_2, to), Some(('c', control_2)))
+            }
+            's' => {
+                let control_2 = point(arguments[0], arguments[1]);
+                let to = point(arguments[2], arguments[3]);
+                (cubic(current, reflected(['c', 's']), control_2, to), Some(('s', control_2)))
+            }
+            'q' => {
+                let control = point(arguments[0], arguments[1]);
+                let to = point(arguments[2], arguments[3]);
+                (quadratic(current, control, to), Some(('q', control)))
+            }
+            't' => {
+                let control = reflected(['q', 't']);
+                let to = point(arguments[0], arguments[1]);
+                (quadratic(current, control, to), Some(('t', control)))
+            }
+            _ => {
+                let to = point(arguments[5], arguments[6]);
+                let radii = (arguments[0], arguments[1]);
+                (arc(current, radii, arguments[2], arguments[3] != 0.0, arguments[4] != 0.0, to), None)
+            }
+        };
+        if ring.points.is_empty() {
+            ring.points.push(current);
+        }
+        if let Some(last) = points.last() {
+            current = *last;
+        }
+        ring.points.extend(points);
+        last_control = control;
+    }
+    finish(&mut ring, &mut rings);
+    rings
+}
+
+fn collect_rings<'a>(
+    element: &'a Element,
+    transform: Transform,
+    matched: bool,
+    ids: &HashMap<&'a str, &'a Element>,
+    // ids of the elements being drawn around this one, so a `<use>` that
+    // refers back to one of them is skipped instead of recursing forever
+    drawing: &mut HashSet<&'a str>,
+    matches: &impl Fn(&ElementTag) -> bool,
+    shapes: &mut Vec<Vec<Ring>>,
+) {
+    let entered = element.id().is_some_and(|id| drawing.insert(id));
+    collect_element_rings(element, transform, matched, ids, drawing, matches, shapes);
+    if entered {
+        drawing.remove(element.id().unwrap_or_default());
+    }
+}
+
+fn collect_element_rings<'a>(
+    element: &'a Element,
+    transform: Transform,
+    matched: bool,
+    ids: &HashMap<&'a str, &'a Element>,
+    drawing: &mut HashSet<&'a str>,
+    matches: &impl Fn(&ElementTag) -> bool,
+    shapes: &mut Vec<Vec<Ring>>,
+) {
+    let matched = matched
+        || element
+            .id()
+            .and_then(|id| ElementTag::parse(id).ok())
+            .is_some_and(|element_tag| matches(&element_tag));
+    let transform = match element.property("transform") {
+        Some(value) => transform.then(&Transform::parse(value)),
+        None => transform,
+    };
+    if element.tag == Tag::Use {
+        let target = element
+            .property("href")
+            .or_else(|| element.property("xlink:href"))
+            .and_then(|href| href.strip_prefix('#'))
+            .filter(|id| !drawing.contains(id))
+            .and_then(|id| ids.get(id));
+        // the referenced element is only drawn here, not where it is defined
+        if let Some(target) = target.filter(|_| matched) {
+            let placed = transform.then(&Transform::translate(length(element, "x"), length(element, "y")));
+            collect_rings(target, placed, true, ids, drawing, matches, shapes);
+        }
+        return;
+    }
+    if matched && element.tag.is_shape() {
+        shapes.push(shape_rings(element).iter().map(|ring| ring.transformed(&transform)).collect());
+    }
+    for child in element.elements() {
+        // templates for `<use>`, never drawn in place
+        if matches!(child.tag.name(), "defs" | "symbol") {
+            continue;
+        }
+        collect_rings(child, transform, matched, ids, drawing, matches, shapes);
+    }
+}
+
+// The rings of every shape at or under an element whose id satisfies
+// `matches`, one entry per shape, with all ancestor `transform`s applied.
+fn shapes(svg: &Svg, matches: impl Fn(&ElementTag) -> bool) -> Vec<Vec<Ring>> {
+    let mut ids = HashMap::new();
+    svg.walk(&mut |_, element| {
+        if let Some(id) = element.id() {
+            ids.insert(id, element);
+        }
+    });
+    let mut shapes = Vec::new();
+    for node in svg.nodes.iter() {
+        if let Node::Element(element) = node {
+            collect_rings(element, Transform::IDENTITY, false, &ids, &mut HashSet::new(), &matches, &mut shapes);
+        }
+    }
+    shapes
+}
+
+// Every shape at or under an element whose id satisfies `matches`, flattened
+// into rings with all ancestor `transform`s applied.
+pub fn rings(svg: &Svg, matches: impl Fn(&ElementTag) -> bool) -> Vec<Ring> {
+    shapes(svg, matches).into_iter().flatten().collect()
+}
+
+// Smallest box around every point of `rings`.
+pub fn ring_bounds<'a>(rings: impl IntoIterator<Item = &'a Ring>) -> Option<ViewBox> {
+    rings
+        .into_iter()
+        .flat_map(|ring| ring.points.iter())
+        .map(|(x, y)| ViewBox { x: *x, y: *y, width: 0.0, height: 0.0 })
+        .reduce(|bounds, point| bounds.union(&point))
+}
+
+// Summed area and perimeter of the matching shapes, with the area-weighted
+// centroid, in drawing units. Within one shape the rings' signed areas add
+// up, so a subpath wound the other way is a hole. Only closed rings have an
+// outline to measure.
+pub fn measure(svg: &Svg, matches: impl Fn(&ElementTag) -> bool) -> Measure {
+    let shapes = shapes(svg, matches);
+    let rings: Vec<&Ring> = shapes.iter().flatten().collect();
+    let perimeter = rings.iter().filter(|ring| ring.closed).map(|ring| ring.perimeter()).sum();
+    // (area, centroid) of every shape that encloses anything
+    let regions: Vec<(f64, (f64, f64))> = shapes
+        .iter()
+        .filter_map(|shape| {
+            let signed_area: f64 = shape.iter().map(Ring::signed_area).sum();
+            if signed_area.abs() < f64::EPSILON {
+                return None;
+            }
+            let (x, y) = shape
+                .iter()
+                .filter_map(|ring| ring.centroid().map(|centroid| (centroid, ring.signed_area())))
+                .fold((0.0, 0.0), |(x, y), (centroid, weight)| (x + centroid.0 * weight, y + centroid.1 * weight));
+            Some((signed_area.abs(), (x / signed_area, y / signed_area)))
+        })
+        .collect();
+    let area: f64 = regions.iter().map(|(area, _)| area).sum();
+    let centroid = if area > 0.0 {
+        let (x, y) = regions
+            .iter()
+            .fold((0.0, 0.0), |(x, y), (weight, centroid)| (x + centroid.0 * weight, y + centroid.1 * weight));
+        Some((x / area, y / area))
+    } else {
+        let centroids: Vec<(f64, f64)> = rings.iter().filter_map(|ring| ring.centroid()).collect();
+        let count = centroids.len() as f64;
+        (!centroids.is_empty()).then(|| {
+            let (x, y) = centroids.iter().fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+            (x / count, y / count)
+        })
+    };
+    Measure { area, perimeter, centroid }
+}
@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{geometry::measure, liquid::Svg, tag::ElementTag};
+
+// Average glyph advance as a share of the font size; labels are short and
+// mostly digits, so this is a fair guess without measuring text.
+const GLYPH_WIDTH: f64 = 0.6;
+
+// A slot's name and where it goes, in drawing coordinates.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Label {
+    pub slot: String,
+    pub text: String,
+    pub at: (f64, f64),
+    pub area: f64,
+}
+impl Label {
+    // Box the text covers at `font_size`, centred on `at`.
+    fn extent(&self, at: (f64, f64), font_size: f64) -> (f64, f64, f64, f64) {
+        let width = self.text.chars().count() as f64 * font_size * GLYPH_WIDTH;
+        (at.0 - width / 2.0, at.1 - font_size / 2.0, width, font_size)
+    }
+}
+
+fn overlaps(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64)) -> bool {
+    a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3
+}
+
+// One label per slot on `floor`, at its centroid, largest rooms first.
+pub fn slot_labels(svg: &Svg, floor: &str) -> Vec<Label> {
+    let mut slots: Vec<ElementTag> = Vec::new();
+    svg.walk(&mut |_, element| {
+        let Some(element_tag) = element.id().and_then(|id| ElementTag::parse(id).ok()) else { return };
+        let on_floor = element_tag.is_slot() && element_tag.floor_token().as_deref() == Some(floor);
+        if on_floor && !slots.iter().any(|slot| slot.slot == element_tag.slot) {
+            slots.push(element_tag);
+        }
+    });
+    let mut labels: Vec<Label> = slots
+        .into_iter()
+        .filter_map(|element_tag| {
+            let slot = element_tag.slot_token()?;
+            let measure = measure(svg, |candidate| {
+                candidate.slot_token().as_ref() == Some(&slot) && candidate.floor_token().as_deref() == Some(floor)
+            });
+            Some(Label { text: element_tag.slot?, at: measure.centroid?, area: measure.area, slot })
+        })
+        .collect();
+    labels.sort_by(|a, b| b.area.total_cmp(&a.area));
+    labels
+}
+
+// Labels that fit at `font_size` (drawing units), each at its centroid or
+// nudged a line up or down. Earlier labels win, the rest are left out.
+pub fn place(labels: &[Label], font_size: f64) -> Vec<Label> {
+    let mut placed: Vec<(Label, (f64, f64, f64, f64))> = Vec::new();
+    for label in labels {
+        let (x, y) = label.at;
+        let spot = [y, y - font_size, y + font_size].into_iter().find_map(|y| {
+            let extent = label.extent((x, y), font_size);
+            (!placed.iter().any(|(_, other)| overlaps(extent, *other))).then_some(((x, y), extent))
+        });
+        if let Some((at, extent)) = spot {
+            placed.push((Label { at, ..label.clone() }, extent));
+        }
+    }
+    placed.into_iter().map(|(label, _)| label).collect()
+}
@@ -0,0 +1,31 @@
+// Floor/slot engine shared by the Yew frontend and the native tools. Nothing in
+// here may depend on the browser.
+
+pub mod a11y;
+pub mod entity;
+pub mod floor;
+pub mod geometry;
+pub mod label;
+pub mod liquid;
+pub mod lint;
+pub mod nest;
+pub mod plan;
+pub mod search;
+pub mod shortcut;
+pub mod site;
+pub mod stack;
+pub mod tag;
+pub mod theme;
+pub mod viewport;
+
+pub use entity::EntityResponse;
+pub use floor::{Floor, FloorOverride};
+pub use geometry::{measure, Measure, Transform};
+pub use label::Label;
+pub use nest::{BuildNestedElement, NestedElement};
+pub use plan::{element_index, highlight_option, mark, produce_option, select_options, slot_tag_at, ElementKey, ProducedOption, ShapeState};
+pub use site::{Placement, SiteBuilding, SiteResponse};
+pub use shortcut::Shortcut;
+pub use tag::{ElementTag, TagError};
+pub use theme::{ShapeStyle, Theme, ThemeOverride};
+pub use viewport::ViewBox;
@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use crate::{
+    liquid::{Svg, Tag},
+    tag::ElementTag,
+};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Issue {
+    pub rule: &'static str,
+    pub id: Option<String>,
+    pub message: String,
+}
+
+pub fn lint(content: &str) -> Vec<Issue> {
+    let svg = match Svg::parse(content) {
+        Ok(svg) => svg,
+        Err(e) => {
+            return vec![Issue { rule: "malformed-svg", id: None, message: e.to_string() }];
+        }
+    };
+
+    let mut issues = Vec::new();
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut floors: BTreeSet<String> = BTreeSet::new();
+    // floor token -> slot token -> canonical ids drawing that slot
+    let mut slots: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    // tag of each element on the current path, to know whether a `<g>` encloses it
+    let mut ancestors: Vec<Tag> = Vec::new();
+
+    svg.walk(&mut |path, element| {
+        ancestors.truncate(path.len() - 1);
+        let in_group = ancestors.contains(&Tag::G);
+        ancestors.push(element.tag.clone());
+
+        let id = element.id().map(|id| id.to_string());
+        if element.tag.is_shape() && !in_group {
+            issues.push(Issue {
+                rule: "shape-outside-group",
+                id: id.clone(),
+                message: format!("<{}> is not inside any <g>", element.tag.name()),
+            });
+        }
+        let id = match id {
+            Some(id) => id,
+            None => return,
+        };
+        *ids.entry(id.clone()).or_insert(0) += 1;
+
+        let element_tag = match ElementTag::parse(&id) {
+            Ok(element_tag) => element_tag,
+            Err(e) => {
+                // Only ids that look like they were meant to follow the grammar are
+                // reported; tool-generated ids such as `Layer_1` are left alone.
+                if id.contains("floor-") || id.contains("slot-") {
+                    issues.push(Issue { rule: "invalid-id", id: Some(id), message: e.to_string() });
+                }
+                return;
+            }
+        };
+        match (element_tag.floor_token(), element_tag.slot_token()) {
+            (Some(floor), Some(slot)) => {
+                floors.insert(floor.clone());
+                // `-N` copies of one id draw the same slot
+                slots.entry(floor).or_default().entry(slot).or_default().push(element_tag.tokens().join("_"));
+            }
+            (Some(floor), None) => {
+                floors.insert(floor);
+            }
+            (None, Some(slot)) => {
+                issues.push(Issue {
+                    rule: "slot-without-floor",
+                    id: Some(id),
+                    message: format!("{} belongs to no floor", slot),
+                });
+            }
+            (None, None) => {}
+        }
+    });
+
+    let mut duplicated: Vec<(&String, &usize)> = ids.iter().filter(|(_, count)| **count > 1).collect();
+    duplicated.sort();
+    for (id, count) in duplicated {
+        issues.push(Issue {
+            rule: "duplicate-id",
+            id: Some(id.clone()),
+            message: format!("id is used {} times", count),
+        });
+    }
+    for (floor, floor_slots) in &slots {
+        for (slot, slot_ids) in floor_slots {
+            let distinct: BTreeSet<&String> = slot_ids.iter().collect();
+            if distinct.len() > 1 {
+                issues.push(Issue {
+                    rule: "duplicate-slot",
+                    id: None,
+                    message: format!(
+                        "{} on {} is drawn by several ids: {}",
+                        slot,
+                        floor,
+                        distinct.into_iter().cloned().collect::<Vec<String>>().join(", ")
+                    ),
+                });
+            }
+        }
+    }
+    for floor in &floors {
+        if !slots.contains_key(floor) {
+            issues.push(Issue {
+                rule: "floor-without-slots",
+                id: None,
+                message: format!("{} has no slots", floor),
+            });
+        }
+    }
+    issues
+}
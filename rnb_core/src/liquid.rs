@@ -0,0 +1,466 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+// Owned SVG tree. Text, comments and attribute values are kept verbatim so that
+// a parse followed by `to_string()` only differs from the input where the
+// entity reducer changed something.
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub reason: &'static str,
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.reason, self.offset)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum Property {
+    Id(String),
+    DataName(String),
+    Style(String),
+    Class(String),
+    Points(String),
+    Other(String, String),
+}
+impl Property {
+    pub fn new(name: &str, value: String) -> Self {
+        match name {
+            "id" => Property::Id(value),
+            "data-name" => Property::DataName(value),
+            "style" => Property::Style(value),
+            "class" => Property::Class(value),
+            "points" => Property::Points(value),
+            _ => Property::Other(name.to_string(), value),
+        }
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            Property::Id(_) => "id",
+            Property::DataName(_) => "data-name",
+            Property::Style(_) => "style",
+            Property::Class(_) => "class",
+            Property::Points(_) => "points",
+            Property::Other(name, _) => name,
+        }
+    }
+    // Name to give the attribute through a plain `setAttribute`, which cannot
+    // place `xlink:href` in its namespace; browsers read the SVG 2 `href` instead.
+    pub fn dom_name(&self) -> &str {
+        match self.name() {
+            "xlink:href" => "href",
+            name => name,
+        }
+    }
+    pub fn value(&self) -> &str {
+        match self {
+            Property::Id(v)
+            | Property::DataName(v)
+            | Property::Style(v)
+            | Property::Class(v)
+            | Property::Points(v)
+            | Property::Other(_, v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum Tag {
+    Svg,
+    G,
+    Polygon,
+    Rect,
+    Circle,
+    Ellipse,
+    Line,
+    Polyline,
+    Path,
+    Use,
+    Other(String),
+}
+impl Tag {
+    // the basic shapes plus `<use>`, i.e. everything a room can be drawn with
+    pub const SHAPES: [&'static str; 8] = ["polygon", "rect", "circle", "ellipse", "line", "polyline", "path", "use"];
+
+    pub fn new(name: &str) -> Self {
+        match name {
+            "svg" => Tag::Svg,
+            "g" => Tag::G,
+            "polygon" => Tag::Polygon,
+            "rect" => Tag::Rect,
+            "circle" => Tag::Circle,
+            "ellipse" => Tag::Ellipse,
+            "line" => Tag::Line,
+            "polyline" => Tag::Polyline,
+            "path" => Tag::Path,
+            "use" => Tag::Use,
+            _ => Tag::Other(name.to_string()),
+        }
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            Tag::Svg => "svg",
+            Tag::G => "g",
+            Tag::Polygon => "polygon",
+            Tag::Rect => "rect",
+            Tag::Circle => "circle",
+            Tag::Ellipse => "ellipse",
+            Tag::Line => "line",
+            Tag::Polyline => "polyline",
+            Tag::Path => "path",
+            Tag::Use => "use",
+            Tag::Other(name) => name,
+        }
+    }
+    pub fn is_shape(&self) -> bool {
+        Self::SHAPES.contains(&self.name())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Element {
+    pub tag: Tag,
+    pub properties: Vec<Property>,
+    pub children: Vec<Node>,
+    pub self_closing: bool,
+}
+impl Element {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.iter().find(|p| p.name() == name).map(|p| p.value())
+    }
+    pub fn id(&self) -> Option<&str> {
+        self.property("id")
+    }
+    pub fn data_name(&self) -> Option<&str> {
+        self.property("data-name")
+    }
+    pub fn set_property(&mut self, property: Property) {
+        match self.properties.iter_mut().find(|p| p.name() == property.name()) {
+            Some(existing) => *existing = property,
+            None => self.properties.push(property),
+        }
+    }
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+    pub fn walk_mut(&mut self, f: &mut impl FnMut(&mut Element)) {
+        f(self);
+        for child in self.children.iter_mut() {
+            if let Node::Element(element) = child {
+                element.walk_mut(f);
+            }
+        }
+    }
+    fn walk_path<'a>(&'a self, path: &mut Vec<usize>, f: &mut impl FnMut(&[usize], &'a Element)) {
+        f(path, self);
+        for (index, child) in self.children.iter().enumerate() {
+            if let Node::Element(element) = child {
+                path.push(index);
+                element.walk_path(path, f);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+    Comment(String),
+    // `<?xml ...?>`, `<!DOCTYPE ...>` and CDATA sections, written back untouched.
+    Raw(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Svg {
+    pub nodes: Vec<Node>,
+}
+impl Svg {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser { input, pos: 0 };
+        let nodes = parser.parse_nodes(None)?;
+        Ok(Svg { nodes })
+    }
+
+    // Pre-order walk over every element; the path holds the child index at each
+    // depth starting from `nodes`.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&[usize], &'a Element)) {
+        let mut path = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Node::Element(element) = node {
+                path.push(index);
+                element.walk_path(&mut path, f);
+                path.pop();
+            }
+        }
+    }
+    pub fn walk_mut(&mut self, f: &mut impl FnMut(&mut Element)) {
+        for node in self.nodes.iter_mut() {
+            if let Node::Element(element) = node {
+                element.walk_mut(f);
+            }
+        }
+    }
+    pub fn node_at(&self, path: &[usize]) -> Option<&Node> {
+        let (first, rest) = path.split_first()?;
+        let mut current = self.nodes.get(*first)?;
+        for index in rest {
+            current = match current {
+                Node::Element(element) => element.children.get(*index)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+    pub fn element_at_mut(&mut self, path: &[usize]) -> Option<&mut Element> {
+        let (first, rest) = path.split_first()?;
+        let mut current = match self.nodes.get_mut(*first)? {
+            Node::Element(element) => element,
+            _ => return None,
+        };
+        for index in rest {
+            current = match current.children.get_mut(*index)? {
+                Node::Element(element) => element,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in &self.nodes {
+            write!(f, "{}", node)?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Element(element) => write!(f, "{}", element),
+            Node::Text(text) => f.write_str(text),
+            Node::Comment(comment) => write!(f, "<!--{}-->", comment),
+            Node::Raw(raw) => f.write_str(raw),
+        }
+    }
+}
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.tag.name())?;
+        for property in &self.properties {
+            // values are always written back double quoted
+            write!(f, " {}=\"{}\"", property.name(), property.value().replace('"', "&quot;"))?;
+        }
+        if self.self_closing && self.children.is_empty() {
+            return f.write_str("/>");
+        }
+        f.write_str(">")?;
+        for child in &self.children {
+            write!(f, "{}", child)?;
+        }
+        write!(f, "</{}>", self.tag.name())
+    }
+}
+
+// Decodes the predefined XML entities and numeric character references kept
+// verbatim in text and attribute values. Unknown entities are left as written.
+pub fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => entity[1..].parse::<u32>().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+    fn error(&self, reason: &'static str) -> ParseError {
+        ParseError { offset: self.pos, reason }
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+    // Consumes everything up to and including `end`, returning the part before it.
+    fn take_until(&mut self, end: &str, reason: &'static str) -> Result<&'a str, ParseError> {
+        match self.rest().find(end) {
+            Some(index) => {
+                let taken = &self.rest()[..index];
+                self.pos += index + end.len();
+                Ok(taken)
+            }
+            None => Err(self.error(reason)),
+        }
+    }
+
+    fn parse_nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, ParseError> {
+        let mut nodes = Vec::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return match parent {
+                    Some(_) => Err(self.error("unclosed element")),
+                    None => Ok(nodes),
+                };
+            }
+            if rest.starts_with("</") {
+                let start = self.pos;
+                self.pos += 2;
+                let name = self.take_until(">", "unterminated closing tag")?.trim();
+                return match parent {
+                    Some(parent) if parent == name => Ok(nodes),
+                    _ => Err(ParseError { offset: start, reason: "mismatched closing tag" }),
+                };
+            }
+            if rest.starts_with("<!--") {
+                self.pos += 4;
+                let comment = self.take_until("-->", "unterminated comment")?;
+                nodes.push(Node::Comment(comment.to_string()));
+            } else if rest.starts_with("<![CDATA[") {
+                let start = self.pos;
+                self.take_until("]]>", "unterminated CDATA section")?;
+                nodes.push(Node::Raw(self.input[start..self.pos].to_string()));
+            } else if rest.starts_with("<?") {
+                let start = self.pos;
+                self.take_until("?>", "unterminated processing instruction")?;
+                nodes.push(Node::Raw(self.input[start..self.pos].to_string()));
+            } else if rest.starts_with("<!") {
+                nodes.push(Node::Raw(self.parse_declaration()?));
+            } else if rest.starts_with('<') {
+                nodes.push(Node::Element(self.parse_element()?));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                nodes.push(Node::Text(rest[..end].to_string()));
+                self.pos += end;
+            }
+        }
+    }
+
+    // `<!DOCTYPE ...>` may carry an internal subset in brackets containing `>`.
+    fn parse_declaration(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let mut depth = 0;
+        for (index, c) in self.rest().char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '>' if depth <= 0 => {
+                    self.pos += index + 1;
+                    return Ok(self.input[start..self.pos].to_string());
+                }
+                _ => {}
+            }
+        }
+        Err(self.error("unterminated declaration"))
+    }
+
+    fn parse_name(&mut self) -> &'a str {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn parse_element(&mut self) -> Result<Element, ParseError> {
+        self.pos += 1;
+        let name = self.parse_name();
+        if name.is_empty() {
+            return Err(self.error("expected tag name"));
+        }
+        let mut properties = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(Element { tag: Tag::new(name), properties, children: Vec::new(), self_closing: true });
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                let children = self.parse_nodes(Some(name))?;
+                return Ok(Element { tag: Tag::new(name), properties, children, self_closing: false });
+            }
+            if rest.is_empty() {
+                return Err(self.error("unterminated tag"));
+            }
+            let property_name = self.parse_name();
+            if property_name.is_empty() {
+                return Err(self.error("expected attribute name"));
+            }
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.parse_value()?
+            } else {
+                String::new()
+            };
+            properties.push(Property::new(property_name, value));
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<String, ParseError> {
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let value = self.take_until(if quote == '"' { "\"" } else { "'" }, "unterminated attribute value")?;
+                Ok(value.to_string())
+            }
+            Some(_) => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                Ok(rest[..end].to_string())
+            }
+            None => Err(self.error("expected attribute value")),
+        }
+    }
+}
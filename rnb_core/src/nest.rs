@@ -0,0 +1,34 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    liquid::Svg,
+    plan::ShapeState,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct NestedElement {
+    // child indices from `Svg::nodes` down to the matched `<g>` or shape
+    pub path: Vec<usize>,
+    pub shape_element_tag_name_value: String,
+}
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BuildNestedElement {
+    pub nests: Vec<NestedElement>,
+    pub svg: Svg,
+}
+impl BuildNestedElement {
+    pub fn build(&mut self) -> String {
+        let mut svg = self.svg.clone();
+
+        for nested_element in &self.nests {
+            if let Some(element) = svg.element_at_mut(&nested_element.path) {
+                element.walk_mut(&mut |shape| {
+                    if shape.tag.is_shape() {
+                        shape.set_property(ShapeState::Highlight.property());
+                    }
+                });
+            }
+        }
+        svg.to_string()
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    liquid::{Node, Property, Svg, Tag},
+    nest::{BuildNestedElement, NestedElement},
+    tag::{ElementTag, TagError},
+};
+
+// (id, grammar token, element index)
+pub type ElementKey = (String, String, usize);
+
+// Written to `data-rnb-state` instead of touching the drawing's own `style`, so
+// a state can be replaced without losing anything. `Theme::stylesheet` gives
+// each state its look.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShapeState {
+    Focus,
+    Unfocus,
+    // a slot on the selected floor that cannot be picked
+    Disabled,
+    Highlight,
+}
+impl ShapeState {
+    pub const ATTRIBUTE: &'static str = "data-rnb-state";
+    // set by the frontend on the shape under the pointer, independent of state
+    pub const HOVER_ATTRIBUTE: &'static str = "data-rnb-hover";
+    // position in a multi-selection, see `select_options`
+    pub const SELECTION_ATTRIBUTE: &'static str = "data-rnb-selection";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShapeState::Focus => "focus",
+            ShapeState::Unfocus => "unfocus",
+            ShapeState::Disabled => "disabled",
+            ShapeState::Highlight => "highlight",
+        }
+    }
+    pub fn property(&self) -> Property {
+        Property::new(Self::ATTRIBUTE, self.as_str().to_string())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ProducedOption {
+    pub svg_content: String,
+    // slot token -> floor token, for the pickable slots of the selected floor
+    pub x: HashMap<String, String>,
+    // floor token -> floor token, for every floor in the plan
+    pub y: HashMap<String, String>,
+    pub element: HashMap<ElementKey, bool>,
+    // ids that did not follow the grammar and were left untouched
+    pub errors: Vec<TagError>,
+}
+
+// Focuses every element on `floor` and hides the rest, collecting the slot and
+// floor options along the way. `element` carries the index built by earlier
+// calls so it keeps growing across floor switches.
+pub fn produce_option(content: &str, floor: &str, element: HashMap<ElementKey, bool>) -> Result<ProducedOption, &'static str> {
+    let mut x: HashMap<String, String> = HashMap::new();
+    let mut y: HashMap<String, String> = HashMap::new();
+    let mut element = element;
+    let mut errors = Vec::new();
+
+    let mut svg = Svg::parse(content).map_err(|_| "malformed svg content")?;
+
+    let mut index: usize = 0;
+    svg.walk_mut(&mut |svg_element| {
+        index += 1;
+        let data_name_value = match svg_element.id() {
+            Some(id) => id.to_string(),
+            None => return,
+        };
+        let element_tag = match ElementTag::parse(&data_name_value) {
+            Ok(element_tag) => element_tag,
+            Err(e) => {
+                errors.push(e);
+                return;
+            }
+        };
+
+        for data_name_property in element_tag.tokens() {
+            element.insert((data_name_value.clone(), data_name_property, index), true);
+        }
+
+        let floor_token = element_tag.floor_token();
+        if let Some(floor_token) = &floor_token {
+            y.insert(floor_token.clone(), floor_token.clone());
+        }
+
+        if floor_token.as_deref() == Some(floor) && element_tag.is_slot() && element_tag.is_disabled() {
+            svg_element.set_property(ShapeState::Disabled.property());
+        } else if floor_token.as_deref() == Some(floor) {
+            if let Some(slot_token) = element_tag.slot_token() {
+                x.insert(slot_token, floor.to_string());
+            }
+            svg_element.set_property(ShapeState::Focus.property());
+        } else {
+            svg_element.set_property(ShapeState::Unfocus.property());
+        }
+    });
+
+    Ok(ProducedOption { svg_content: svg.to_string(), x, y, element, errors })
+}
+
+// 1-based pre-order position of the element at `path`, the same numbering
+// `produce_option` uses for the index in `ElementKey`.
+pub fn element_index(svg: &Svg, path: &[usize]) -> Option<usize> {
+    let mut index: usize = 0;
+    let mut found = None;
+    svg.walk(&mut |element_path, _| {
+        index += 1;
+        if found.is_none() && element_path == path {
+            found = Some(index);
+        }
+    });
+    found
+}
+
+// Tag of the nearest element at or above `path` whose id names a slot, so a
+// shape nested in anonymous groups still resolves to its room.
+pub fn slot_tag_at(svg: &Svg, path: &[usize]) -> Option<ElementTag> {
+    (1..=path.len()).rev().find_map(|len| match svg.node_at(&path[..len]) {
+        Some(Node::Element(element)) => element
+            .id()
+            .and_then(|id| ElementTag::parse(id).ok())
+            .filter(|element_tag| element_tag.is_slot()),
+        _ => None,
+    })
+}
+
+// `<g>` and shape elements whose id names `slot` on `floor`.
+fn slot_nests(svg: &Svg, slot: &str, floor: &str) -> Vec<NestedElement> {
+    let mut nests = Vec::new();
+    svg.walk(&mut |path, svg_element| {
+        if svg_element.tag != Tag::G && !svg_element.tag.is_shape() {
+            return;
+        }
+        let element_tag = match svg_element.id().map(ElementTag::parse) {
+            Some(Ok(element_tag)) => element_tag,
+            _ => return,
+        };
+
+        let equal_slot: bool = element_tag.slot_token().as_deref() == Some(slot) &&
+        element_tag.floor_token().as_deref() == Some(floor);
+
+        if equal_slot {
+            nests.push(NestedElement {
+                path: path.to_vec(),
+                shape_element_tag_name_value: svg_element.tag.name().to_owned(),
+            });
+        }
+    });
+    nests
+}
+
+// Highlights `slot` on `floor` in content from `produce_option` or from an
+// earlier highlight, whose highlighted shapes go back to focus first. Disabled
+// slots are refused.
+pub fn highlight_option(content: &str, slot: &str, floor: &str) -> Result<String, &'static str> {
+    let mut svg = Svg::parse(content).map_err(|_| "malformed svg content")?;
+    let mut disabled = false;
+    svg.walk(&mut |_, element| {
+        disabled |= element
+            .id()
+            .and_then(|id| ElementTag::parse(id).ok())
+            .is_some_and(|element_tag| {
+                element_tag.is_disabled()
+                    && element_tag.slot_token().as_deref() == Some(slot)
+                    && element_tag.floor_token().as_deref() == Some(floor)
+            });
+    });
+    if disabled {
+        return Err("slot cannot be picked");
+    }
+    svg.walk_mut(&mut |element| {
+        if element.property(ShapeState::ATTRIBUTE) == Some(ShapeState::Highlight.as_str()) {
+            element.set_property(ShapeState::Focus.property());
+        }
+    });
+
+    let mut nest: BuildNestedElement = BuildNestedElement {
+        nests: slot_nests(&svg, slot, floor),
+        svg,
+    };
+    Ok(nest.build())
+}
+
+// Marks the shapes of every `(slot token, floor token)` in `selection` that
+// lies on `floor` with its position in `selection`, which picks its colour.
+pub fn select_options(content: &str, selection: &[(String, String)], floor: &str) -> Result<String, &'static str> {
+    let mut svg = Svg::parse(content).map_err(|_| "malformed svg content")?;
+
+    for (position, (slot, slot_floor)) in selection.iter().enumerate() {
+        if slot_floor != floor {
+            continue;
+        }
+        for nested_element in slot_nests(&svg, slot, floor) {
+            if let Some(element) = svg.element_at_mut(&nested_element.path) {
+                element.walk_mut(&mut |shape| {
+                    if shape.tag.is_shape() {
+                        shape.set_property(Property::new(ShapeState::SELECTION_ATTRIBUTE, position.to_string()));
+                    }
+                });
+            }
+        }
+    }
+    Ok(svg.to_string())
+}
+
+// What the plan shows: `slot`, if any, highlighted on `floor` in content from
+// `produce_option`, with the multi-`selection` marked on top.
+pub fn mark(content: &str, slot: Option<&str>, selection: &[(String, String)], floor: &str) -> Result<String, &'static str> {
+    let content = match slot {
+        Some(slot) => highlight_option(content, slot, floor)?,
+        None => content.to_string(),
+    };
+    if selection.is_empty() {
+        return Ok(content);
+    }
+    select_options(&content, selection, floor)
+}
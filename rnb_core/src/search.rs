@@ -0,0 +1,42 @@
+// Bonus for a character right after the previous match, so that "101" finds
+// "slot 101" before "1 0 1".
+const RUN_BONUS: i64 = 5;
+// Bonus for a match at the start of a word.
+const WORD_BONUS: i64 = 3;
+
+// How well `query` matches `text` as a case-insensitive subsequence, or `None`
+// when it does not. Whitespace in the query is ignored.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let mut from = 0;
+    for wanted in query {
+        let offset = text[from..].iter().position(|c| *c == wanted)?;
+        let index = from + offset;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += RUN_BONUS;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += WORD_BONUS;
+        }
+        // later matches are worth a little less than early ones
+        score -= offset as i64 / 4;
+        previous = Some(index);
+        from = index + 1;
+    }
+    Some(score)
+}
+
+// `items` matching `query`, best first, ties kept in their original order.
+// An empty query matches everything.
+pub fn rank<'a, T>(items: &'a [T], query: &str, text: impl Fn(&T) -> String) -> Vec<&'a T> {
+    let mut scored: Vec<(i64, &T)> = items
+        .iter()
+        .filter_map(|item| fuzzy_score(query, &text(item)).map(|score| (score, item)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
@@ -0,0 +1,96 @@
+// Keyboard commands of the plan viewer. Keys are `KeyboardEvent.key` values,
+// so nothing here needs the browser.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shortcut {
+    FloorUp,
+    FloorDown,
+    NextSlot,
+    PreviousSlot,
+    OpenDetails,
+    Clear,
+    FocusSearch,
+    ZoomIn,
+    ZoomOut,
+    Help,
+}
+impl Shortcut {
+    // in the order the help overlay lists them
+    pub const ALL: [Shortcut; 10] = [
+        Shortcut::FloorUp,
+        Shortcut::FloorDown,
+        Shortcut::NextSlot,
+        Shortcut::PreviousSlot,
+        Shortcut::OpenDetails,
+        Shortcut::Clear,
+        Shortcut::FocusSearch,
+        Shortcut::ZoomIn,
+        Shortcut::ZoomOut,
+        Shortcut::Help,
+    ];
+
+    // `tab` says whether Tab may cycle slots; it is left alone while the user
+    // is tabbing through controls.
+    pub fn from_key(key: &str, shift: bool, tab: bool) -> Option<Self> {
+        match key {
+            "PageUp" => Some(Shortcut::FloorUp),
+            "PageDown" => Some(Shortcut::FloorDown),
+            "ArrowRight" | "ArrowDown" => Some(Shortcut::NextSlot),
+            "ArrowLeft" | "ArrowUp" => Some(Shortcut::PreviousSlot),
+            "Tab" if tab && shift => Some(Shortcut::PreviousSlot),
+            "Tab" if tab => Some(Shortcut::NextSlot),
+            "Enter" => Some(Shortcut::OpenDetails),
+            "Escape" => Some(Shortcut::Clear),
+            "/" => Some(Shortcut::FocusSearch),
+            "+" | "=" => Some(Shortcut::ZoomIn),
+            "-" | "_" => Some(Shortcut::ZoomOut),
+            "?" => Some(Shortcut::Help),
+            _ => None,
+        }
+    }
+
+    pub fn keys(&self) -> &'static str {
+        match self {
+            Shortcut::FloorUp => "Page Up",
+            Shortcut::FloorDown => "Page Down",
+            Shortcut::NextSlot => "→ ↓ Tab",
+            Shortcut::PreviousSlot => "← ↑ Shift+Tab",
+            Shortcut::OpenDetails => "Enter",
+            Shortcut::Clear => "Esc",
+            Shortcut::FocusSearch => "/",
+            Shortcut::ZoomIn => "+",
+            Shortcut::ZoomOut => "−",
+            Shortcut::Help => "?",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Shortcut::FloorUp => "Floor above",
+            Shortcut::FloorDown => "Floor below",
+            Shortcut::NextSlot => "Next slot on this floor",
+            Shortcut::PreviousSlot => "Previous slot on this floor",
+            Shortcut::OpenDetails => "Open the slot's details",
+            Shortcut::Clear => "Clear the highlight",
+            Shortcut::FocusSearch => "Search slots",
+            Shortcut::ZoomIn => "Zoom in",
+            Shortcut::ZoomOut => "Zoom out",
+            Shortcut::Help => "Show or hide this help",
+        }
+    }
+}
+
+// The slot `offset` places from `current` in `slots`, wrapping around. With
+// nothing highlighted, stepping forward starts at the first slot and stepping
+// back at the last.
+pub fn cycle<'a>(slots: &'a [String], current: Option<&str>, offset: isize) -> Option<&'a String> {
+    if slots.is_empty() {
+        return None;
+    }
+    let count = slots.len() as isize;
+    let index = match current.and_then(|current| slots.iter().position(|slot| slot == current)) {
+        Some(index) => index as isize + offset,
+        None if offset < 0 => count + offset,
+        None => offset - 1,
+    };
+    slots.get(index.rem_euclid(count) as usize)
+}
@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    entity::EntityResponse,
+    geometry::{ring_bounds, rings, Ring, Transform},
+    liquid::Svg,
+    viewport::ViewBox,
+};
+
+// Where a building sits on its site: its drawing is rotated about its own
+// origin by `rotation` degrees, then moved by `x`, `y` site units.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Placement {
+    pub code: String,
+    pub x: f64,
+    pub y: f64,
+    pub rotation: f64,
+}
+impl Placement {
+    pub fn transform(&self) -> Transform {
+        Transform::translate(self.x, self.y).then(&Transform::rotate(self.rotation))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SiteBuilding {
+    pub placement: Placement,
+    pub entity: EntityResponse,
+}
+
+// Several neighbouring entities shown in one scene.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SiteResponse {
+    pub name: String,
+    pub buildings: Vec<SiteBuilding>,
+}
+
+// A building's default floor as placed on the site.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footprint {
+    pub code: String,
+    pub name: String,
+    pub rings: Vec<Ring>,
+}
+
+// Falls back to every floor when the default one draws nothing.
+pub fn footprint(building: &SiteBuilding) -> Footprint {
+    let entity = &building.entity;
+    let svg = entity
+        .svg_content
+        .as_deref()
+        .or(entity.svg_raw_content.as_deref())
+        .and_then(|content| Svg::parse(content).ok());
+    let mut footprint = Footprint { code: building.placement.code.clone(), name: entity.name.clone(), rings: Vec::new() };
+    let Some(svg) = svg else { return footprint };
+    let mut outline = rings(&svg, |element_tag| element_tag.floor_token().as_deref() == Some(entity.default_floor.as_str()));
+    if outline.is_empty() {
+        outline = rings(&svg, |element_tag| element_tag.floor.is_some());
+    }
+    let transform = building.placement.transform();
+    footprint.rings = outline.iter().map(|ring| ring.transformed(&transform)).collect();
+    footprint
+}
+
+pub fn site_bounds(footprints: &[Footprint]) -> Option<ViewBox> {
+    ring_bounds(footprints.iter().flat_map(|footprint| footprint.rings.iter()))
+}
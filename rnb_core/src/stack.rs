@@ -0,0 +1,43 @@
+use crate::{
+    geometry::{ring_bounds, rings, Ring, Transform},
+    liquid::Svg,
+    viewport::ViewBox,
+};
+
+// cos 30° and sin 30°, the isometric axes.
+const COS_30: f64 = 0.866_025_403_784_438_6;
+const SIN_30: f64 = 0.5;
+
+// One floor of the exploded building, already projected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    pub floor: String,
+    pub rings: Vec<Ring>,
+}
+
+// Isometric projection of the floor `level` storeys up, each storey lifted
+// `spacing` drawing units above the one below.
+pub fn isometric(level: usize, spacing: f64) -> Transform {
+    Transform { a: COS_30, b: SIN_30, c: -COS_30, d: SIN_30, e: 0.0, f: -(level as f64) * spacing }
+}
+
+// Every floor in `floors`, bottom first, projected so they stack upwards.
+pub fn exploded(svg: &Svg, floors: &[String], spacing: f64) -> Vec<Layer> {
+    floors
+        .iter()
+        .enumerate()
+        .map(|(level, floor)| {
+            let transform = isometric(level, spacing);
+            let rings = rings(svg, |element_tag| element_tag.floor_token().as_ref() == Some(floor))
+                .iter()
+                .map(|ring| ring.transformed(&transform))
+                .collect();
+            Layer { floor: floor.clone(), rings }
+        })
+        .collect()
+}
+
+// Smallest box around every projected point.
+pub fn layer_bounds(layers: &[Layer]) -> Option<ViewBox> {
+    ring_bounds(layers.iter().flat_map(|layer| layer.rings.iter()))
+}
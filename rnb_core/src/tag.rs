@@ -0,0 +1,158 @@
+//! Element id grammar, version 1.
+//!
+//! Every `id` that takes part in floor/slot selection is a list of `key-value`
+//! segments joined by `_`, optionally followed by the `-N` suffix drawing tools
+//! append when an id is duplicated:
+//!
+//! ```text
+//! id       = segment *( "_" segment ) [ "-" copy ]
+//! segment  = key "-" value
+//! key      = "building" / "floor" / "zone" / "slot" / "kind" / "tag"
+//! value    = 1*( ALPHA / DIGIT / "." )
+//! copy     = 1*DIGIT
+//! ```
+//!
+//! Each key except `tag` may appear at most once; `tag` may repeat. Segment
+//! order is free, so `slot-101_floor-2` and `floor-2_slot-101` name the same
+//! slot. A floor is any element with a `floor` segment, a slot any element with
+//! both `floor` and `slot` segments. A slot tagged `tag-disabled` is drawn but
+//! cannot be picked.
+
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+pub const GRAMMAR_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum TagErrorKind {
+    Empty,
+    EmptySegment,
+    MissingValue(String),
+    UnknownKey(String),
+    InvalidValue(String),
+    DuplicateKey(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TagError {
+    pub id: String,
+    // byte offset of the offending segment inside `id`
+    pub offset: usize,
+    pub kind: TagErrorKind,
+}
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match &self.kind {
+            TagErrorKind::Empty => "empty id".to_string(),
+            TagErrorKind::EmptySegment => "empty segment".to_string(),
+            TagErrorKind::MissingValue(key) => format!("segment \"{}\" has no value", key),
+            TagErrorKind::UnknownKey(key) => format!("unknown key \"{}\"", key),
+            TagErrorKind::InvalidValue(value) => format!("invalid value \"{}\"", value),
+            TagErrorKind::DuplicateKey(key) => format!("key \"{}\" given twice", key),
+        };
+        write!(f, "id \"{}\" (v{}) at byte {}: {}", self.id, GRAMMAR_VERSION, self.offset, reason)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ElementTag {
+    pub building: Option<String>,
+    pub floor: Option<String>,
+    pub zone: Option<String>,
+    pub slot: Option<String>,
+    pub kind: Option<String>,
+    pub tags: Vec<String>,
+    pub copy: Option<u32>,
+}
+impl ElementTag {
+    pub const DISABLED_TAG: &'static str = "disabled";
+
+    pub fn parse(id: &str) -> Result<Self, TagError> {
+        let error = |offset: usize, kind: TagErrorKind| TagError { id: id.to_string(), offset, kind };
+        if id.is_empty() {
+            return Err(error(0, TagErrorKind::Empty));
+        }
+
+        let mut tag = ElementTag::default();
+        let mut offset = 0;
+        let segments: Vec<&str> = id.split('_').collect();
+        let last = segments.len() - 1;
+        for (position, segment) in segments.into_iter().enumerate() {
+            if segment.is_empty() {
+                return Err(error(offset, TagErrorKind::EmptySegment));
+            }
+            let (key, mut value) = match segment.split_once('-') {
+                Some((key, value)) if !value.is_empty() => (key, value),
+                _ => return Err(error(offset, TagErrorKind::MissingValue(segment.to_string()))),
+            };
+            if position == last {
+                if let Some((head, copy)) = value.rsplit_once('-') {
+                    match copy.parse::<u32>() {
+                        Ok(copy) if !head.is_empty() => {
+                            tag.copy = Some(copy);
+                            value = head;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+                return Err(error(offset + key.len() + 1, TagErrorKind::InvalidValue(value.to_string())));
+            }
+            let field = match key {
+                "building" => &mut tag.building,
+                "floor" => &mut tag.floor,
+                "zone" => &mut tag.zone,
+                "slot" => &mut tag.slot,
+                "kind" => &mut tag.kind,
+                "tag" => {
+                    tag.tags.push(value.to_string());
+                    offset += segment.len() + 1;
+                    continue;
+                }
+                _ => return Err(error(offset, TagErrorKind::UnknownKey(key.to_string()))),
+            };
+            if field.is_some() {
+                return Err(error(offset, TagErrorKind::DuplicateKey(key.to_string())));
+            }
+            *field = Some(value.to_string());
+            offset += segment.len() + 1;
+        }
+        Ok(tag)
+    }
+
+    pub fn floor_token(&self) -> Option<String> {
+        self.floor.as_ref().map(|floor| format!("floor-{}", floor))
+    }
+    pub fn slot_token(&self) -> Option<String> {
+        self.slot.as_ref().map(|slot| format!("slot-{}", slot))
+    }
+    pub fn is_slot(&self) -> bool {
+        self.floor.is_some() && self.slot.is_some()
+    }
+    pub fn is_disabled(&self) -> bool {
+        self.tags.iter().any(|tag| tag == Self::DISABLED_TAG)
+    }
+
+    // Every segment in canonical `key-value` form, without the copy suffix.
+    pub fn tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let keyed = [
+            ("building", &self.building),
+            ("floor", &self.floor),
+            ("zone", &self.zone),
+            ("slot", &self.slot),
+            ("kind", &self.kind),
+        ];
+        for (key, value) in keyed {
+            if let Some(value) = value {
+                tokens.push(format!("{}-{}", key, value));
+            }
+        }
+        for value in &self.tags {
+            tokens.push(format!("tag-{}", value));
+        }
+        tokens
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{liquid::Tag, plan::ShapeState};
+
+// Presentation properties applied to a shape in one state. Unset properties
+// leave the drawing's own value alone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct ShapeStyle {
+    pub stroke: Option<String>,
+    pub fill: Option<String>,
+    pub stroke_width: Option<String>,
+}
+impl ShapeStyle {
+    pub fn new(stroke: Option<&str>, fill: Option<&str>, stroke_width: Option<&str>) -> Self {
+        Self {
+            stroke: stroke.map(str::to_string),
+            fill: fill.map(str::to_string),
+            stroke_width: stroke_width.map(str::to_string),
+        }
+    }
+
+    // Every set property as an `!important` declaration, so it wins over the
+    // classes the drawing was exported with.
+    pub fn css(&self) -> String {
+        [("stroke", &self.stroke), ("fill", &self.fill), ("stroke-width", &self.stroke_width)]
+            .iter()
+            .filter_map(|(name, value)| value.as_ref().map(|value| format!("{}: {} !important", name, value)))
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+
+    // Only the values that are a colour, or a length for `stroke_width`, so a
+    // value cannot end its declaration and start rules of its own.
+    pub fn sanitised(&self) -> ShapeStyle {
+        ShapeStyle {
+            stroke: self.stroke.clone().filter(|value| is_colour(value)),
+            fill: self.fill.clone().filter(|value| is_colour(value)),
+            stroke_width: self.stroke_width.clone().filter(|value| is_length(value)),
+        }
+    }
+
+    // `other`'s set properties win.
+    pub fn merged(&self, other: &ShapeStyle) -> ShapeStyle {
+        ShapeStyle {
+            stroke: other.stroke.clone().or(self.stroke.clone()),
+            fill: other.fill.clone().or(self.fill.clone()),
+            stroke_width: other.stroke_width.clone().or(self.stroke_width.clone()),
+        }
+    }
+}
+
+// A keyword such as `red` or `none`, a hex colour, or an `rgb()`/`hsl()` call.
+fn is_colour(value: &str) -> bool {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return [3, 4, 6, 8].contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    if let Some((function, arguments)) = value.strip_suffix(')').and_then(|value| value.split_once('(')) {
+        return ["rgb", "rgba", "hsl", "hsla"].contains(&function.to_ascii_lowercase().as_str())
+            && arguments.chars().all(|c| c.is_ascii_digit() || " ,.%/-".contains(c));
+    }
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+// A number with an optional unit, e.g. `2`, `1.5px` or `10%`.
+fn is_length(value: &str) -> bool {
+    let value = value.trim();
+    let number = value.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%');
+    number.parse::<f64>().is_ok_and(f64::is_finite) && value[number.len()..].len() <= 3
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    // shapes on the selected floor
+    pub focus: ShapeStyle,
+    // shapes on every other floor
+    pub unfocus: ShapeStyle,
+    // the selected slot
+    pub highlight: ShapeStyle,
+    pub hover: ShapeStyle,
+    // the slot holding keyboard focus
+    pub focus_ring: ShapeStyle,
+    // every slot in a multi-selection, under its `palette` colour
+    pub selected: ShapeStyle,
+    // one entry per selection position, so compared slots stay apart
+    pub palette: Vec<ShapeStyle>,
+    // slots that cannot be picked, and the muted base of the overview drawings
+    pub disabled: ShapeStyle,
+    // `fill` is the text colour, `stroke` the halo around it
+    pub label: ShapeStyle,
+    pub background: String,
+}
+fn palette(colours: &[(&str, &str)]) -> Vec<ShapeStyle> {
+    colours
+        .iter()
+        .map(|(stroke, fill)| ShapeStyle::new(Some(stroke), Some(fill), None))
+        .collect()
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+impl Theme {
+    pub const PRESETS: [&'static str; 3] = ["light", "dark", "high-contrast"];
+    // slot shapes the plan makes reachable by keyboard
+    pub const FOCUS_RING_SELECTOR: &'static str = "[role=\"button\"]:focus-visible";
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            focus: ShapeStyle::new(Some("blue"), None, None),
+            unfocus: ShapeStyle::new(Some("none"), Some("none"), None),
+            highlight: ShapeStyle::new(Some("red"), Some("red"), None),
+            hover: ShapeStyle::new(Some("orange"), Some("rgba(255, 165, 0, 0.3)"), None),
+            focus_ring: ShapeStyle::new(Some("#005fcc"), None, Some("3")),
+            selected: ShapeStyle::new(None, None, Some("2")),
+            palette: palette(&[
+                ("#1f77b4", "rgba(31, 119, 180, 0.35)"),
+                ("#ff7f0e", "rgba(255, 127, 14, 0.35)"),
+                ("#2ca02c", "rgba(44, 160, 44, 0.35)"),
+                ("#9467bd", "rgba(148, 103, 189, 0.35)"),
+                ("#8c564b", "rgba(140, 86, 75, 0.35)"),
+                ("#e377c2", "rgba(227, 119, 194, 0.35)"),
+            ]),
+            disabled: ShapeStyle::new(Some("#999999"), Some("#e6e6e6"), None),
+            label: ShapeStyle::new(Some("white"), Some("#1d1d1b"), Some("3px")),
+            background: "#ffffff".to_string(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            focus: ShapeStyle::new(Some("#8ab4f8"), Some("#2b2b2b"), None),
+            unfocus: ShapeStyle::new(Some("none"), Some("none"), None),
+            highlight: ShapeStyle::new(Some("#f28b82"), Some("#5c2b29"), None),
+            hover: ShapeStyle::new(Some("#fdd663"), Some("rgba(253, 214, 99, 0.25)"), None),
+            focus_ring: ShapeStyle::new(Some("#ffffff"), None, Some("3")),
+            selected: ShapeStyle::new(None, None, Some("2")),
+            palette: palette(&[
+                ("#8ab4f8", "rgba(138, 180, 248, 0.3)"),
+                ("#fcad70", "rgba(252, 173, 112, 0.3)"),
+                ("#81c995", "rgba(129, 201, 149, 0.3)"),
+                ("#c58af9", "rgba(197, 138, 249, 0.3)"),
+                ("#e6c9a8", "rgba(230, 201, 168, 0.3)"),
+                ("#ff8bcb", "rgba(255, 139, 203, 0.3)"),
+            ]),
+            disabled: ShapeStyle::new(Some("#5f6368"), Some("#3c4043"), None),
+            label: ShapeStyle::new(Some("#202124"), Some("#e8eaed"), Some("3px")),
+            background: "#202124".to_string(),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            focus: ShapeStyle::new(Some("black"), Some("white"), Some("2")),
+            unfocus: ShapeStyle::new(Some("none"), Some("none"), None),
+            highlight: ShapeStyle::new(Some("black"), Some("yellow"), Some("4")),
+            hover: ShapeStyle::new(Some("blue"), Some("cyan"), Some("4")),
+            focus_ring: ShapeStyle::new(Some("magenta"), None, Some("6")),
+            selected: ShapeStyle::new(Some("black"), None, Some("4")),
+            palette: palette(&[
+                ("black", "lime"),
+                ("black", "cyan"),
+                ("black", "magenta"),
+                ("black", "orange"),
+                ("black", "#aaaaff"),
+                ("black", "#ff6666"),
+            ]),
+            disabled: ShapeStyle::new(Some("black"), Some("#cccccc"), Some("1")),
+            label: ShapeStyle::new(Some("white"), Some("black"), Some("4px")),
+            background: "#ffffff".to_string(),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "light" => Some(Self::light()),
+            "dark" => Some(Self::dark()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    // Rules for every `data-rnb-state` value, for hovering and for the
+    // keyboard focus, which replaces the browser's box outline. Shape selectors
+    // all share one specificity, so where states overlap the later rule wins:
+    // a disabled slot and a highlight show through their floor's focus.
+    pub fn stylesheet(&self) -> String {
+        let state = |state: ShapeState| format!("[{}=\"{}\"]", ShapeState::ATTRIBUTE, state.as_str());
+        let mut rules = vec![
+            (state(ShapeState::Focus), self.focus.clone()),
+            (state(ShapeState::Unfocus), self.unfocus.clone()),
+            (state(ShapeState::Disabled), self.disabled.clone()),
+        ];
+        for (position, colour) in self.palette.iter().enumerate() {
+            rules.push((
+                format!("[{}=\"{}\"]", ShapeState::SELECTION_ATTRIBUTE, position),
+                self.selected.merged(colour),
+            ));
+        }
+        rules.push((state(ShapeState::Highlight), self.highlight.clone()));
+        rules.push((format!("[{}]", ShapeState::HOVER_ATTRIBUTE), self.hover.clone()));
+        rules.push((Self::FOCUS_RING_SELECTOR.to_string(), self.focus_ring.clone()));
+        rules
+            .iter()
+            .filter(|(_, style)| !style.css().is_empty())
+            .map(|(selector, style)| {
+                let selectors: Vec<String> = std::iter::once(selector.clone())
+                    .chain(Tag::SHAPES.iter().flat_map(|shape| [format!("{}{}", shape, selector), format!("{} {}", selector, shape)]))
+                    .collect();
+                format!("{} {{ {} }}\n", selectors.join(", "), style.css())
+            })
+            .chain((!self.focus_ring.css().is_empty()).then(|| format!("{} {{ outline: none }}\n", Self::FOCUS_RING_SELECTOR)))
+            .collect()
+    }
+
+    // Override values that are not a colour or length are ignored, since they
+    // end up in the plan's stylesheet.
+    pub fn with_override(&self, theme_override: &ThemeOverride) -> Self {
+        let merge = |base: &ShapeStyle, other: &Option<ShapeStyle>| match other {
+            Some(other) => base.merged(&other.sanitised()),
+            None => base.clone(),
+        };
+        Self {
+            name: self.name.clone(),
+            focus: merge(&self.focus, &theme_override.focus),
+            unfocus: merge(&self.unfocus, &theme_override.unfocus),
+            highlight: merge(&self.highlight, &theme_override.highlight),
+            hover: merge(&self.hover, &theme_override.hover),
+            focus_ring: merge(&self.focus_ring, &theme_override.focus_ring),
+            selected: merge(&self.selected, &theme_override.selected),
+            palette: match &theme_override.palette {
+                Some(palette) => palette.iter().map(ShapeStyle::sanitised).collect(),
+                None => self.palette.clone(),
+            },
+            disabled: merge(&self.disabled, &theme_override.disabled),
+            label: merge(&self.label, &theme_override.label),
+            background: theme_override.background.clone().filter(|value| is_colour(value)).unwrap_or(self.background.clone()),
+        }
+    }
+}
+
+// Per-entity adjustments applied on top of whichever preset the user picked,
+// e.g. a campus colour for highlights.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct ThemeOverride {
+    pub focus: Option<ShapeStyle>,
+    pub unfocus: Option<ShapeStyle>,
+    pub highlight: Option<ShapeStyle>,
+    pub hover: Option<ShapeStyle>,
+    pub focus_ring: Option<ShapeStyle>,
+    pub selected: Option<ShapeStyle>,
+    // replaces the whole palette
+    pub palette: Option<Vec<ShapeStyle>>,
+    pub disabled: Option<ShapeStyle>,
+    pub label: Option<ShapeStyle>,
+    pub background: Option<String>,
+}
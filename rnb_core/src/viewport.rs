@@ -0,0 +1,132 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    geometry::{ring_bounds, rings, Ring},
+    liquid::{Node, Svg, Tag},
+    tag::ElementTag,
+};
+
+// The `viewBox` of the root `<svg>`: the part of the drawing that is visible.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ViewBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+impl fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.x, self.y, self.width, self.height)
+    }
+}
+impl ViewBox {
+    pub fn parse(value: &str) -> Option<Self> {
+        let numbers = numbers(value);
+        match numbers[..] {
+            [x, y, width, height] if width > 0.0 && height > 0.0 => Some(Self { x, y, width, height }),
+            _ => None,
+        }
+    }
+
+    // The root `viewBox`, or `0 0 width height` for drawings that only size
+    // themselves.
+    pub fn of(svg: &Svg) -> Option<Self> {
+        let root = svg.nodes.iter().find_map(|node| match node {
+            Node::Element(element) if element.tag == Tag::Svg => Some(element),
+            _ => None,
+        })?;
+        if let Some(view_box) = root.property("viewBox").and_then(Self::parse) {
+            return Some(view_box);
+        }
+        let length = |name: &str| root.property(name).and_then(|value| value.trim_end_matches("px").trim().parse::<f64>().ok());
+        match (length("width"), length("height")) {
+            (Some(width), Some(height)) if width > 0.0 && height > 0.0 => Some(Self { x: 0.0, y: 0.0, width, height }),
+            _ => None,
+        }
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    // `factor` above 1 zooms in. `at` stays put on screen.
+    pub fn zoom(&self, factor: f64, at: (f64, f64)) -> Self {
+        let (at_x, at_y) = at;
+        Self {
+            x: at_x - (at_x - self.x) / factor,
+            y: at_y - (at_y - self.y) / factor,
+            width: self.width / factor,
+            height: self.height / factor,
+        }
+    }
+
+    pub fn centered_on(&self, point: (f64, f64)) -> Self {
+        Self { x: point.0 - self.width / 2.0, y: point.1 - self.height / 2.0, ..*self }
+    }
+
+    pub fn pan(&self, dx: f64, dy: f64) -> Self {
+        Self { x: self.x + dx, y: self.y + dy, ..*self }
+    }
+
+    // Grows every side by `ratio` of the larger dimension, so a fitted shape
+    // does not touch the edges.
+    pub fn padded(&self, ratio: f64) -> Self {
+        let padding = self.width.max(self.height) * ratio;
+        Self {
+            x: self.x - padding,
+            y: self.y - padding,
+            width: self.width + padding * 2.0,
+            height: self.height + padding * 2.0,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+
+    // Drawing units per screen pixel in a `client` sized box, under the
+    // default `preserveAspectRatio` (xMidYMid meet).
+    pub fn units_per_pixel(&self, client: (f64, f64)) -> f64 {
+        let (client_width, client_height) = client;
+        (self.width / client_width).max(self.height / client_height)
+    }
+
+    // Maps a point in a `client` sized box to drawing coordinates.
+    pub fn client_to_view(&self, point: (f64, f64), client: (f64, f64)) -> (f64, f64) {
+        let scale = self.units_per_pixel(client);
+        let (center_x, center_y) = self.center();
+        let (client_width, client_height) = client;
+        (
+            center_x + (point.0 - client_width / 2.0) * scale,
+            center_y + (point.1 - client_height / 2.0) * scale,
+        )
+    }
+}
+
+fn numbers(value: &str) -> Vec<f64> {
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|number| !number.is_empty())
+        .filter_map(|number| number.parse::<f64>().ok())
+        .collect()
+}
+
+// Every shape at or under an element whose id satisfies `matches`, as
+// `points` values in drawing coordinates, in document order.
+pub fn outline(svg: &Svg, matches: impl Fn(&ElementTag) -> bool) -> Vec<String> {
+    rings(svg, matches).iter().map(Ring::points_value).collect()
+}
+
+// Bounds of the shapes `outline` finds.
+pub fn bounds(svg: &Svg, matches: impl Fn(&ElementTag) -> bool) -> Option<ViewBox> {
+    ring_bounds(&rings(svg, matches))
+}
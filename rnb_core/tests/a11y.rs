@@ -0,0 +1,23 @@
+use rnb_core::{a11y::{plan_name, slot_name}, ElementTag, Floor};
+
+#[test]
+fn slot_name_reads_the_id_then_the_floor() {
+    let element_tag = ElementTag::parse("building-north_floor-2_zone-a_slot-101_kind-office_tag-quiet").unwrap();
+    assert_eq!(
+        slot_name(&element_tag, &Floor::from_token("floor-2")),
+        "Slot 101, office, zone a, building north, tagged quiet, Floor 2"
+    );
+}
+
+#[test]
+fn slot_name_uses_the_floor_label() {
+    let element_tag = ElementTag::parse("floor-b1_slot-7").unwrap();
+    assert_eq!(slot_name(&element_tag, &Floor::from_token("floor-b1")), "Slot 7, Basement 1 (B1)");
+}
+
+#[test]
+fn plan_name_counts_slots() {
+    let floor = Floor::from_token("floor-0");
+    assert_eq!(plan_name(&floor, 1), "Ground floor (G) plan, 1 slot");
+    assert_eq!(plan_name(&floor, 3), "Ground floor (G) plan, 3 slots");
+}
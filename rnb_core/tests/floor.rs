@@ -0,0 +1,39 @@
+use rnb_core::floor::{ordered, step, Floor, FloorOverride};
+
+fn tokens(tokens: &[&str]) -> Vec<String> {
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+#[test]
+fn reads_levels_from_tokens() {
+    let second = Floor::from_token("floor-2");
+    assert_eq!((second.level, second.name.as_str(), second.alias), (2.0, "Floor 2", None));
+    let basement = Floor::from_token("floor-b1");
+    assert_eq!((basement.level, basement.label().as_str()), (-1.0, "Basement 1 (B1)"));
+    assert_eq!(Floor::from_token("floor-0").label(), "Ground floor (G)");
+    assert_eq!(Floor::from_token("floor-G").level, 0.0);
+    assert_eq!(Floor::from_token("floor-roof").name, "roof");
+}
+
+#[test]
+fn orders_numerically_with_basements_first() {
+    let floors = ordered(&tokens(&["floor-10", "floor-2", "floor-b1", "floor-1"]), &[]);
+    let order: Vec<&str> = floors.iter().map(|floor| floor.token.as_str()).collect();
+    assert_eq!(order, ["floor-b1", "floor-1", "floor-2", "floor-10"]);
+}
+
+#[test]
+fn overrides_rename_and_move_floors() {
+    let overrides = [FloorOverride {
+        token: "floor-m".to_string(),
+        level: Some(1.5),
+        name: Some("Mezzanine".to_string()),
+        alias: Some("M".to_string()),
+        ..FloorOverride::default()
+    }];
+    let floors = ordered(&tokens(&["floor-2", "floor-m", "floor-1"]), &overrides);
+    assert_eq!(floors[1].label(), "Mezzanine (M)");
+    assert_eq!(step(&floors, "floor-m", 1).map(|floor| floor.token.as_str()), Some("floor-2"));
+    assert_eq!(step(&floors, "floor-1", -1), None);
+    assert_eq!(step(&floors, "floor-9", 1), None);
+}
@@ -0,0 +1,123 @@
+use rnb_core::{geometry::{measure, rings, Transform}, liquid::Svg};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+const SHAPES: &str = include_str!("fixtures/shapes.svg");
+const TRANSFORMS: &str = include_str!("fixtures/transforms.svg");
+const XLINK: &str = include_str!("fixtures/xlink.svg");
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() < 1e-6 * expected.abs().max(1.0)
+}
+
+fn close_point(actual: Option<(f64, f64)>, expected: (f64, f64)) -> bool {
+    actual.is_some_and(|(x, y)| close(x, expected.0) && close(y, expected.1))
+}
+
+#[test]
+fn parses_transform_lists() {
+    let transform = Transform::parse("translate(10, 20) scale(2)");
+    assert_eq!(transform.apply((1.0, 1.0)), (12.0, 22.0));
+    let rotated = Transform::parse("rotate(90 10 10)").apply((20.0, 10.0));
+    assert!(close(rotated.0, 10.0) && close(rotated.1, 20.0));
+    assert_eq!(Transform::parse("matrix(1 0 0 1 5 6)").apply((0.0, 0.0)), (5.0, 6.0));
+    assert_eq!(Transform::parse("perspective(2)"), Transform::IDENTITY);
+}
+
+#[test]
+fn measures_polygons() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    let slot = measure(&svg, |element_tag| element_tag.slot_token().as_deref() == Some("slot-101"));
+    assert_eq!(slot.area, 180.0 * 130.0);
+    assert_eq!(slot.perimeter, 2.0 * (180.0 + 130.0));
+    assert_eq!(slot.centroid, Some((100.0, 75.0)));
+    let scaled = slot.scaled(10.0);
+    assert_eq!((scaled.area, scaled.perimeter), (234.0, 62.0));
+    let missing = measure(&svg, |element_tag| element_tag.slot.as_deref() == Some("999"));
+    assert_eq!((missing.area, missing.centroid), (0.0, None));
+}
+
+#[test]
+fn measures_every_shape_type() {
+    let svg = Svg::parse(SHAPES).unwrap();
+    let slot = |name: &str| measure(&svg, |element_tag| element_tag.slot.as_deref() == Some(name));
+    assert_eq!(slot("rect").area, 5000.0);
+    let circle = slot("circle");
+    assert!((circle.area - std::f64::consts::PI * 625.0).abs() / circle.area < 0.005);
+    assert!(close_point(circle.centroid, (150.0, 25.0)));
+    // the open polyline and line add neither area nor perimeter
+    let deep = slot("deep");
+    assert_eq!(deep.area, 2500.0);
+    assert!(close(deep.perimeter, 150.0 + 50f64.hypot(100.0)));
+    let desk = slot("use");
+    assert_eq!(desk.area, 200.0);
+    assert!(close_point(desk.centroid, (20.0, 175.0)));
+}
+
+#[test]
+fn applies_ancestor_transforms() {
+    let svg = Svg::parse(TRANSFORMS).unwrap();
+    let slot = |name: &str| measure(&svg, |element_tag| element_tag.slot.as_deref() == Some(name));
+    let scaled = slot("scaled");
+    assert!(close(scaled.area, 400.0) && close_point(scaled.centroid, (110.0, 10.0)));
+    let rotated = slot("rotated");
+    assert!(close(rotated.area, 200.0) && close_point(rotated.centroid, (195.0, 10.0)));
+    let bay = slot("bay");
+    assert!(close(bay.area, 450.0) && close_point(bay.centroid, (715.0, 7.5)));
+}
+
+#[test]
+fn flattens_path_commands() {
+    let svg = Svg::parse(TRANSFORMS).unwrap();
+    let relative = measure(&svg, |element_tag| element_tag.slot.as_deref() == Some("relative"));
+    assert_eq!((relative.area, relative.perimeter), (1200.0, 140.0));
+    assert!(close_point(relative.centroid, (120.0, 115.0)));
+    let round = rings(&svg, |element_tag| element_tag.slot.as_deref() == Some("round"));
+    assert_eq!(round.len(), 1);
+    assert!(round[0].closed);
+    // a half disc of radius 10
+    assert!((round[0].area() - std::f64::consts::PI * 50.0).abs() < 0.5);
+}
+
+#[test]
+fn resolves_xlink_references() {
+    let svg = Svg::parse(XLINK).unwrap();
+    let bay = measure(&svg, |element_tag| element_tag.slot.as_deref() == Some("a"));
+    assert_eq!(bay.area, 800.0);
+    assert!(close_point(bay.centroid, (30.0, 20.0)));
+}
+
+
+#[test]
+fn subpaths_wound_the_other_way_are_holes() {
+    // a 100x100 building around a 40x40 courtyard drawn counter-clockwise
+    let svg = Svg::parse(r#"<svg><g id="floor-1">
+        <path id="slot-court_floor-1" d="M 0 0 H 100 V 100 H 0 Z M 30 30 V 70 H 70 V 30 Z"/>
+    </g></svg>"#).unwrap();
+    let court = measure(&svg, |element_tag| element_tag.slot.as_deref() == Some("court"));
+    assert_eq!(court.area, 10000.0 - 1600.0);
+    assert_eq!(court.perimeter, 400.0 + 160.0);
+    assert!(close_point(court.centroid, (50.0, 50.0)));
+}
+
+#[test]
+fn use_cycles_are_skipped() {
+    let svg = Svg::parse(r##"<svg><g id="floor-1">
+        <g id="slot-a_floor-1"><rect width="10" height="10"/><use href="#slot-a_floor-1"/></g>
+        <g id="slot-b_floor-1"><use id="to-c" href="#slot-c_floor-1"/></g>
+        <g id="slot-c_floor-1"><rect width="5" height="5"/><use href="#slot-b_floor-1"/></g>
+    </g></svg>"##).unwrap();
+    let slot = |name: &str| measure(&svg, |element_tag| element_tag.slot.as_deref() == Some(name));
+    assert_eq!(slot("a").area, 100.0);
+    // b draws c and c draws b, each stopping where it would come back round
+    assert_eq!(slot("b").area, 25.0);
+    assert_eq!(slot("c").area, 25.0);
+}
+
+#[test]
+fn reads_compact_arc_flags() {
+    let spaced = Svg::parse(r#"<svg><path id="slot-a_floor-1" d="M0 0 h10 v10 h-10 z M20 0 a5 5 0 1 1 10 0 z"/></svg>"#).unwrap();
+    let compact = Svg::parse(r#"<svg><path id="slot-a_floor-1" d="M0 0h10v10h-10z M20 0a5 5 0 1110 0z"/></svg>"#).unwrap();
+    let area = |svg: &Svg| measure(svg, |element_tag| element_tag.slot.is_some()).area;
+    assert!(area(&spaced) > 100.0);
+    assert!(close(area(&compact), area(&spaced)));
+}
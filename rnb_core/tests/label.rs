@@ -0,0 +1,32 @@
+use rnb_core::{label::{place, slot_labels}, liquid::Svg, Label};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+
+fn label(text: &str, at: (f64, f64)) -> Label {
+    Label { slot: format!("slot-{}", text), text: text.to_string(), at, area: 0.0 }
+}
+
+#[test]
+fn labels_slots_on_the_floor_at_their_centroids() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    let labels = slot_labels(&svg, "floor-1");
+    let texts: Vec<&str> = labels.iter().map(|label| label.text.as_str()).collect();
+    // largest first
+    assert_eq!(texts, ["103", "102", "101"]);
+    assert_eq!(labels[2].at, (100.0, 75.0));
+    assert_eq!(labels[2].slot, "slot-101");
+    assert_eq!(slot_labels(&svg, "floor-2").len(), 1);
+    assert!(slot_labels(&svg, "floor-9").is_empty());
+}
+
+#[test]
+fn placement_nudges_then_drops_colliding_labels() {
+    let labels = [label("101", (50.0, 50.0)), label("102", (52.0, 50.0)), label("103", (54.0, 50.0)), label("104", (56.0, 50.0))];
+    let placed = place(&labels, 10.0);
+    let spots: Vec<(&str, (f64, f64))> = placed.iter().map(|label| (label.text.as_str(), label.at)).collect();
+    assert_eq!(spots, [("101", (50.0, 50.0)), ("102", (52.0, 40.0)), ("103", (54.0, 60.0))]);
+    // smaller text fits side by side without nudging
+    let spread = [label("101", (0.0, 0.0)), label("102", (20.0, 0.0))];
+    assert_eq!(place(&spread, 5.0)[1].at, (20.0, 0.0));
+    assert_eq!(place(&spread, 20.0)[1].at, (20.0, -20.0));
+}
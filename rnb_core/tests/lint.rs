@@ -0,0 +1,50 @@
+use rnb_core::lint::lint;
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+
+fn rules(content: &str) -> Vec<&'static str> {
+    lint(content).into_iter().map(|issue| issue.rule).collect()
+}
+
+#[test]
+fn clean_plan_has_no_issues() {
+    assert!(lint(TWO_FLOORS).is_empty());
+}
+
+#[test]
+fn reports_structural_problems() {
+    let content = r#"<svg>
+        <g id="floor-1"><polygon id="slot-a_floor-1"/><polygon id="slot-a_floor-1_kind-hall"/><polygon id="slot-b"/></g>
+        <g id="floor-2"/>
+        <polygon id="room-12_floor-1"/>
+        <rect id="slot-d_floor-1"/>
+        <g><polygon id="slot-c_floor-1"/><polygon id="slot-c_floor-1"/></g>
+    </svg>"#;
+    let mut found = rules(content);
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            "duplicate-id",
+            "duplicate-slot",
+            "floor-without-slots",
+            "invalid-id",
+            "shape-outside-group",
+            "shape-outside-group",
+            "slot-without-floor",
+        ]
+    );
+}
+
+#[test]
+fn copies_of_an_id_are_one_slot() {
+    let content = r#"<svg>
+        <g id="floor-1"><polygon id="slot-a_floor-1"/><polygon id="slot-a_floor-1-2"/><rect id="floor-1_slot-a-3"/></g>
+    </svg>"#;
+    assert!(lint(content).is_empty());
+}
+
+#[test]
+fn reports_malformed_svg() {
+    assert_eq!(rules("<svg><g></svg>"), ["malformed-svg"]);
+}
@@ -0,0 +1,66 @@
+use rnb_core::liquid::{unescape, Node, Property, Svg};
+
+const XLINK: &str = include_str!("fixtures/xlink.svg");
+
+#[test]
+fn round_trips_untouched_content() {
+    let content = "<?xml version=\"1.0\"?>\n<!DOCTYPE svg [<!ENTITY a \"b\">]>\n<svg><!-- note --><g id=\"floor-1\"><polygon points=\"0 0 1 1\"/></g><style><![CDATA[.a{fill:red}]]></style><text>Caf\u{e9} &amp; bar</text></svg>";
+    assert_eq!(Svg::parse(content).unwrap().to_string(), content);
+}
+
+#[test]
+fn normalises_single_quoted_attributes() {
+    let svg = Svg::parse("<svg><g id='floor-1' data-name='say \"hi\"'/></svg>").unwrap();
+    assert_eq!(svg.to_string(), "<svg><g id=\"floor-1\" data-name=\"say &quot;hi&quot;\"/></svg>");
+}
+
+#[test]
+fn keeps_attribute_values_unescaped() {
+    let svg = Svg::parse("<svg><g id='floor-1' data-name='say \"hi\"'/></svg>").unwrap();
+    match svg.node_at(&[0, 0]) {
+        Some(Node::Element(element)) => assert_eq!(element.property("data-name"), Some("say \"hi\"")),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn unquoted_values_may_contain_slashes() {
+    let svg = Svg::parse("<svg><image href=plans/floor-1.png ></image></svg>").unwrap();
+    assert_eq!(svg.to_string(), "<svg><image href=\"plans/floor-1.png\"></image></svg>");
+}
+
+#[test]
+fn finds_nodes_by_path() {
+    let svg = Svg::parse("<svg><g><polygon id=\"a\"/>text</g></svg>").unwrap();
+    match svg.node_at(&[0, 0, 0]) {
+        Some(Node::Element(element)) => assert_eq!(element.id(), Some("a")),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(svg.node_at(&[0, 0, 1]), Some(&Node::Text("text".to_string())));
+    assert_eq!(svg.node_at(&[0, 0, 2]), None);
+}
+
+#[test]
+fn rejects_mismatched_tags() {
+    let error = Svg::parse("<svg><g></svg>").unwrap_err();
+    assert_eq!(error.reason, "mismatched closing tag");
+    assert_eq!(error.offset, 8);
+}
+
+#[test]
+fn unescapes_entities() {
+    assert_eq!(unescape("a &amp; b &lt;c&gt; &quot;&#65;&#x42;&apos; &nbsp; & done"), "a & b <c> \"AB' &nbsp; & done");
+}
+
+#[test]
+fn xlink_href_is_set_as_href() {
+    let svg = Svg::parse(XLINK).unwrap();
+    let mut names = Vec::new();
+    svg.walk(&mut |_, element| {
+        if element.tag.name() == "use" {
+            names.push(element.properties.iter().map(Property::dom_name).collect::<Vec<&str>>());
+        }
+    });
+    assert_eq!(names, [vec!["id", "href", "x", "y"], vec!["id", "href", "x", "y", "href"]]);
+    assert_eq!(Property::new("points", "0 0".to_string()).dom_name(), "points");
+}
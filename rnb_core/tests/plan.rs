@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use rnb_core::{element_index, highlight_option, liquid::{Svg, Tag}, mark, produce_option, select_options, slot_tag_at, ShapeState};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+const NESTED_GROUPS: &str = include_str!("fixtures/nested_groups.svg");
+const SHAPES: &str = include_str!("fixtures/shapes.svg");
+
+fn state_of(content: &str, id: &str) -> Option<String> {
+    let svg = Svg::parse(content).unwrap();
+    let mut state = None;
+    svg.walk(&mut |_, element| {
+        if element.id() == Some(id) {
+            state = element.property(ShapeState::ATTRIBUTE).map(|s| s.to_string());
+        }
+    });
+    state
+}
+
+// points of every highlighted polygon, in document order
+fn highlighted(content: &str) -> Vec<String> {
+    let svg = Svg::parse(content).unwrap();
+    let mut points = Vec::new();
+    svg.walk(&mut |_, element| {
+        if element.tag == Tag::Polygon && element.property(ShapeState::ATTRIBUTE) == Some("highlight") {
+            points.push(element.property("points").unwrap_or("").to_string());
+        }
+    });
+    points
+}
+
+#[test]
+fn lists_floors_and_slots_of_selected_floor() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+
+    let mut floors: Vec<&String> = produced.y.keys().collect();
+    floors.sort();
+    assert_eq!(floors, ["floor-1", "floor-2"]);
+
+    let mut slots: Vec<&String> = produced.x.keys().collect();
+    slots.sort();
+    assert_eq!(slots, ["slot-101", "slot-102", "slot-103"]);
+    assert!(produced.x.values().all(|floor| floor == "floor-1"));
+
+    // `Layer_1` is not part of the grammar and is reported, not styled
+    assert_eq!(produced.errors.len(), 1);
+    assert_eq!(produced.errors[0].id, "Layer_1");
+    assert_eq!(state_of(&produced.svg_content, "Layer_1"), None);
+}
+
+#[test]
+fn switching_floor_moves_focus() {
+    let first = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    assert_eq!(state_of(&first.svg_content, "floor-1").as_deref(), Some("focus"));
+    assert_eq!(state_of(&first.svg_content, "floor-2").as_deref(), Some("unfocus"));
+
+    let second = produce_option(&first.svg_content, "floor-2", first.element.clone()).unwrap();
+    assert_eq!(second.x.keys().collect::<Vec<&String>>(), ["slot-201"]);
+    assert_eq!(state_of(&second.svg_content, "slot-101_floor-1").as_deref(), Some("unfocus"));
+    assert_eq!(state_of(&second.svg_content, "slot-201_floor-2").as_deref(), Some("focus"));
+    // the element index is keyed by document position, so it does not grow on re-render
+    assert_eq!(second.element.len(), first.element.len());
+}
+
+#[test]
+fn unrelated_content_survives_a_round_trip() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    assert!(produced.svg_content.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(produced.svg_content.contains(".cls-1 { fill: #e6e6e6;"));
+    assert!(produced.svg_content.contains("Café — Ground"));
+}
+
+#[test]
+fn highlights_only_the_selected_slot() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let content = highlight_option(&produced.svg_content, "slot-102", "floor-1").unwrap();
+    assert_eq!(highlighted(&content), ["200 10 390 10 390 140 200 140 200 10"]);
+
+    // a slot drawn directly as a polygon
+    let content = highlight_option(&produced.svg_content, "slot-103", "floor-1").unwrap();
+    assert_eq!(highlighted(&content), ["10 150 390 150 390 290 10 290 10 150"]);
+}
+
+#[test]
+fn slot_on_another_floor_is_not_highlighted() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let content = highlight_option(&produced.svg_content, "slot-201", "floor-1").unwrap();
+    assert!(highlighted(&content).is_empty());
+}
+
+#[test]
+fn re_highlighting_replaces_previous_highlight() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let first = highlight_option(&produced.svg_content, "slot-103", "floor-1").unwrap();
+    let first = highlight_option(&first, "slot-101", "floor-1").unwrap();
+    assert_eq!(highlighted(&first), ["10 10 190 10 190 140 10 140 10 10"]);
+
+    let content = highlight_option(&first, "slot-102", "floor-1").unwrap();
+    assert_eq!(highlighted(&content), ["200 10 390 10 390 140 200 140 200 10"]);
+    assert_eq!(state_of(&content, "slot-103_floor-1_kind-hall").as_deref(), Some("focus"));
+    let svg = Svg::parse(&content).unwrap();
+    let mut slot_101 = None;
+    svg.walk(&mut |_, element| {
+        if element.property("points") == Some("10 10 190 10 190 140 10 140 10 10") {
+            slot_101 = element.property(ShapeState::ATTRIBUTE).map(|state| state.to_string());
+        }
+    });
+    assert_eq!(slot_101.as_deref(), Some("focus"));
+}
+
+#[test]
+fn highlights_copies_and_deeply_nested_shapes() {
+    let produced = produce_option(NESTED_GROUPS, "floor-3", HashMap::new()).unwrap();
+    let mut slots: Vec<&String> = produced.x.keys().collect();
+    slots.sort();
+    assert_eq!(slots, ["slot-301", "slot-302"]);
+
+    let content = highlight_option(&produced.svg_content, "slot-301", "floor-3").unwrap();
+    assert_eq!(highlighted(&content), ["0,0 100,0 100,100 0,100", "0,100 100,100 100,200 0,200"]);
+    // the drawing's own fill is left in place
+    assert!(content.contains("style=\"fill: #ffcc00\""));
+}
+
+#[test]
+fn element_index_matches_the_element_map() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let svg = Svg::parse(&produced.svg_content).unwrap();
+    let mut path = None;
+    svg.walk(&mut |element_path, element| {
+        if element.id() == Some("slot-102_floor-1") {
+            path = Some(element_path.to_vec());
+        }
+    });
+    let index = element_index(&svg, &path.unwrap()).unwrap();
+    assert!(produced.element.contains_key(&("slot-102_floor-1".to_string(), "slot-102".to_string(), index)));
+    assert_eq!(element_index(&svg, &[99]), None);
+}
+
+#[test]
+fn slot_tag_at_resolves_nested_polygons() {
+    let svg = Svg::parse(NESTED_GROUPS).unwrap();
+    let mut polygons = Vec::new();
+    svg.walk(&mut |path, element| {
+        if element.tag == Tag::Polygon {
+            polygons.push(path.to_vec());
+        }
+    });
+    let first = slot_tag_at(&svg, &polygons[0]).unwrap();
+    assert_eq!(first.slot.as_deref(), Some("301"));
+    assert_eq!(first.zone.as_deref(), Some("east"));
+    assert_eq!(slot_tag_at(&svg, &polygons[2]).unwrap().slot_token().as_deref(), Some("slot-302"));
+    assert_eq!(slot_tag_at(&svg, &polygons[0][..2]), None);
+}
+
+#[test]
+fn disabled_slots_are_drawn_but_not_picked() {
+    let content = r#"<svg><g id="floor-1">
+        <g id="slot-a_floor-1_tag-disabled"><rect width="10" height="10"/></g>
+        <rect id="slot-b_floor-1"/>
+    </g><g id="floor-2"><rect id="slot-c_floor-2_tag-disabled"/></g></svg>"#;
+    let produced = produce_option(content, "floor-1", HashMap::new()).unwrap();
+    assert_eq!(produced.x.keys().collect::<Vec<&String>>(), ["slot-b"]);
+    assert_eq!(state_of(&produced.svg_content, "slot-a_floor-1_tag-disabled").as_deref(), Some("disabled"));
+    // only the shown floor's slots are disabled, the rest stay out of focus
+    assert_eq!(state_of(&produced.svg_content, "slot-c_floor-2_tag-disabled").as_deref(), Some("unfocus"));
+
+    assert_eq!(highlight_option(&produced.svg_content, "slot-a", "floor-1"), Err("slot cannot be picked"));
+    assert!(highlight_option(&produced.svg_content, "slot-b", "floor-1").is_ok());
+}
+
+#[test]
+fn malformed_content_is_an_error() {
+    assert!(produce_option("<svg><g></svg>", "floor-1", HashMap::new()).is_err());
+    assert!(highlight_option("<svg>", "slot-1", "floor-1").is_err());
+}
+
+#[test]
+fn highlights_every_shape_type() {
+    let produced = produce_option(SHAPES, "floor-1", HashMap::new()).unwrap();
+    let highlighted_tags = |slot: &str| {
+        let content = highlight_option(&produced.svg_content, slot, "floor-1").unwrap();
+        let svg = Svg::parse(&content).unwrap();
+        let mut tags = Vec::new();
+        svg.walk(&mut |_, element| {
+            if element.property(ShapeState::ATTRIBUTE) == Some("highlight") {
+                tags.push(element.tag.name().to_string());
+            }
+        });
+        tags
+    };
+    assert_eq!(highlighted_tags("slot-rect"), ["rect"]);
+    assert_eq!(highlighted_tags("slot-circle"), ["circle"]);
+    assert_eq!(highlighted_tags("slot-ellipse"), ["ellipse"]);
+    assert_eq!(highlighted_tags("slot-deep"), ["path", "polyline", "line"]);
+    assert_eq!(highlighted_tags("slot-use"), ["use"]);
+    // the referenced shape itself is left alone
+    assert!(!highlighted_tags("slot-use").contains(&"rect".to_string()));
+}
+
+#[test]
+fn selection_marks_slots_on_the_floor_by_position() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let selection = [
+        ("slot-201".to_string(), "floor-2".to_string()),
+        ("slot-103".to_string(), "floor-1".to_string()),
+        ("slot-101".to_string(), "floor-1".to_string()),
+    ];
+    let content = select_options(&produced.svg_content, &selection, "floor-1").unwrap();
+    let svg = Svg::parse(&content).unwrap();
+    let mut marked = Vec::new();
+    svg.walk(&mut |_, element| {
+        if let Some(position) = element.property(ShapeState::SELECTION_ATTRIBUTE) {
+            marked.push((position.to_string(), element.property("points").unwrap_or("").to_string()));
+        }
+    });
+    assert_eq!(marked, [
+        ("2".to_string(), "10 10 190 10 190 140 10 140 10 10".to_string()),
+        ("1".to_string(), "10 150 390 150 390 290 10 290 10 150".to_string()),
+    ]);
+}
+
+#[test]
+fn clearing_the_highlight_keeps_the_selection() {
+    let produced = produce_option(TWO_FLOORS, "floor-1", HashMap::new()).unwrap();
+    let selection = [("slot-102".to_string(), "floor-1".to_string())];
+    let highlighted_content = mark(&produced.svg_content, Some("slot-101"), &selection, "floor-1").unwrap();
+    assert_eq!(highlighted(&highlighted_content).len(), 1);
+
+    let cleared = mark(&produced.svg_content, None, &selection, "floor-1").unwrap();
+    assert!(!cleared.contains(r#"data-rnb-state="highlight""#));
+    assert!(cleared.contains(r#"data-rnb-selection="0""#));
+    assert_eq!(mark(&produced.svg_content, None, &[], "floor-1").unwrap(), produced.svg_content);
+}
@@ -0,0 +1,24 @@
+use rnb_core::search::{fuzzy_score, rank};
+
+#[test]
+fn matches_subsequences_ignoring_case_and_spaces() {
+    assert!(fuzzy_score("mtg", "Meeting room").is_some());
+    assert!(fuzzy_score("MEET rm", "meeting room").is_some());
+    assert_eq!(fuzzy_score("xyz", "meeting room"), None);
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+}
+
+#[test]
+fn prefers_runs_and_word_starts() {
+    assert!(fuzzy_score("101", "slot 101") > fuzzy_score("101", "1 0 1"));
+    assert!(fuzzy_score("room", "room 1") > fuzzy_score("room", "broom 1"));
+}
+
+#[test]
+fn ranks_best_first_and_keeps_ties_in_order() {
+    let slots = ["lab 2", "library", "lobby", "lab 1"];
+    let ranked = rank(&slots, "lab", |slot| slot.to_string());
+    assert_eq!(ranked, [&"lab 2", &"lab 1"]);
+    assert_eq!(rank(&slots, "lb", |slot| slot.to_string()), [&"lab 2", &"library", &"lobby", &"lab 1"]);
+    assert_eq!(rank(&slots, "", |slot| slot.to_string()).len(), 4);
+}
@@ -0,0 +1,28 @@
+use rnb_core::{shortcut::cycle, Shortcut};
+
+fn slots(slots: &[&str]) -> Vec<String> {
+    slots.iter().map(|slot| slot.to_string()).collect()
+}
+
+#[test]
+fn maps_keys_to_shortcuts() {
+    assert_eq!(Shortcut::from_key("PageUp", false, false), Some(Shortcut::FloorUp));
+    assert_eq!(Shortcut::from_key("ArrowLeft", false, false), Some(Shortcut::PreviousSlot));
+    assert_eq!(Shortcut::from_key("Tab", true, true), Some(Shortcut::PreviousSlot));
+    // Tab keeps moving focus between controls unless the plan has it
+    assert_eq!(Shortcut::from_key("Tab", false, false), None);
+    assert_eq!(Shortcut::from_key("=", false, false), Some(Shortcut::ZoomIn));
+    assert_eq!(Shortcut::from_key("a", false, false), None);
+    assert!(Shortcut::ALL.iter().all(|shortcut| !shortcut.keys().is_empty() && !shortcut.description().is_empty()));
+}
+
+#[test]
+fn cycles_slots_with_wrap_around() {
+    let slots = slots(&["slot-101", "slot-102", "slot-103"]);
+    assert_eq!(cycle(&slots, Some("slot-103"), 1).unwrap(), "slot-101");
+    assert_eq!(cycle(&slots, Some("slot-101"), -1).unwrap(), "slot-103");
+    assert_eq!(cycle(&slots, None, 1).unwrap(), "slot-101");
+    assert_eq!(cycle(&slots, None, -1).unwrap(), "slot-103");
+    assert_eq!(cycle(&slots, Some("slot-999"), 1).unwrap(), "slot-101");
+    assert_eq!(cycle(&[], None, 1), None);
+}
@@ -0,0 +1,58 @@
+use rnb_core::{
+    site::{footprint, site_bounds},
+    EntityResponse, Placement, SiteBuilding, SiteResponse, ViewBox,
+};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+
+fn building(code: &str, x: f64, rotation: f64, default_floor: &str) -> SiteBuilding {
+    SiteBuilding {
+        placement: Placement { code: code.to_string(), x, y: 0.0, rotation },
+        entity: EntityResponse {
+            name: code.to_uppercase(),
+            svg_raw_content: Some(TWO_FLOORS.to_string()),
+            svg_content: None,
+            default_floor: default_floor.to_string(),
+            theme: None,
+            units_per_metre: None,
+            floors: Vec::new(),
+        },
+    }
+}
+
+fn rounded(view_box: ViewBox) -> (i64, i64, i64, i64) {
+    let round = |value: f64| value.round() as i64;
+    (round(view_box.x), round(view_box.y), round(view_box.width), round(view_box.height))
+}
+
+#[test]
+fn places_the_default_floor() {
+    let north = footprint(&building("north", 1000.0, 0.0, "floor-2"));
+    assert_eq!((north.code.as_str(), north.name.as_str(), north.rings.len()), ("north", "NORTH", 1));
+    assert_eq!(north.rings[0].points[0], (1010.0, 10.0));
+    // an unknown default floor shows every floor instead
+    assert_eq!(footprint(&building("south", 0.0, 0.0, "floor-9")).rings.len(), 4);
+}
+
+#[test]
+fn rotates_about_the_building_origin() {
+    let turned = footprint(&building("east", 0.0, 90.0, "floor-2"));
+    let bounds = site_bounds(std::slice::from_ref(&turned)).unwrap();
+    assert_eq!(rounded(bounds), (-290, 10, 280, 380));
+}
+
+#[test]
+fn bounds_cover_every_building() {
+    let footprints = [footprint(&building("a", 0.0, 0.0, "floor-2")), footprint(&building("b", 500.0, 0.0, "floor-2"))];
+    assert_eq!(rounded(site_bounds(&footprints).unwrap()), (10, 10, 880, 280));
+    assert_eq!(site_bounds(&[]), None);
+}
+
+#[test]
+fn reads_site_json_with_default_placement() {
+    let site: SiteResponse = serde_json::from_str(
+        r#"{"name": "Campus", "buildings": [{"placement": {"code": "north"}, "entity": {"name": "North", "svg_raw_content": null, "svg_content": null, "default_floor": "floor-1"}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(site.buildings[0].placement, Placement { code: "north".to_string(), ..Placement::default() });
+}
@@ -0,0 +1,35 @@
+use rnb_core::{liquid::Svg, stack::{exploded, isometric, layer_bounds}};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+
+fn close(actual: (f64, f64), expected: (f64, f64)) -> bool {
+    (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9
+}
+
+#[test]
+fn projects_isometrically_and_lifts_each_level() {
+    let ground = isometric(0, 100.0);
+    assert!(close(ground.apply((0.0, 0.0)), (0.0, 0.0)));
+    assert!(close(ground.apply((2.0, 2.0)), (0.0, 2.0)));
+    assert!(close(ground.apply((2.0, 0.0)), (3f64.sqrt(), 1.0)));
+    assert!(close(isometric(2, 100.0).apply((0.0, 0.0)), (0.0, -200.0)));
+}
+
+#[test]
+fn stacks_floors_bottom_first() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    let floors = ["floor-1".to_string(), "floor-2".to_string()];
+    let layers = exploded(&svg, &floors, 50.0);
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].floor, "floor-1");
+    assert_eq!((layers[0].rings.len(), layers[1].rings.len()), (3, 1));
+    // the same corner on the upper floor sits one spacing higher
+    let lower = layers[0].rings[0].points[0];
+    let upper = layers[1].rings[0].points[0];
+    assert!(close(upper, (lower.0, lower.1 - 50.0)));
+    let bounds = layer_bounds(&layers).unwrap();
+    // the top corner of the upper floor is the highest point
+    assert_eq!(bounds.y, upper.1);
+    assert!(layers[1].rings[0].points_value().starts_with("0,-40 "));
+    assert_eq!(layer_bounds(&[]), None);
+}
@@ -0,0 +1,43 @@
+use rnb_core::tag::{ElementTag, TagErrorKind};
+
+#[test]
+fn parses_segments_in_any_order() {
+    let a = ElementTag::parse("slot-101_floor-2").unwrap();
+    let b = ElementTag::parse("floor-2_slot-101").unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.floor_token().as_deref(), Some("floor-2"));
+    assert_eq!(a.slot_token().as_deref(), Some("slot-101"));
+    assert!(a.is_slot());
+}
+
+#[test]
+fn strips_copy_suffix() {
+    let tag = ElementTag::parse("slot-a_floor-1-2").unwrap();
+    assert_eq!(tag.copy, Some(2));
+    assert_eq!(tag.tokens(), ["floor-1", "slot-a"]);
+}
+
+#[test]
+fn collects_repeated_tags() {
+    let tag = ElementTag::parse("building-n_floor-B1_zone-east_slot-7_kind-lab_tag-wet_tag-gas").unwrap();
+    assert_eq!(
+        tag.tokens(),
+        ["building-n", "floor-B1", "zone-east", "slot-7", "kind-lab", "tag-wet", "tag-gas"]
+    );
+}
+
+#[test]
+fn errors_point_at_the_offending_segment() {
+    let error = ElementTag::parse("floor-1_room-12").unwrap_err();
+    assert_eq!(error.offset, 8);
+    assert_eq!(error.kind, TagErrorKind::UnknownKey("room".to_string()));
+
+    let error = ElementTag::parse("floor-1_floor-2").unwrap_err();
+    assert_eq!(error.kind, TagErrorKind::DuplicateKey("floor".to_string()));
+
+    let error = ElementTag::parse("slot-a!_floor-1").unwrap_err();
+    assert_eq!(error.offset, 5);
+
+    let error = ElementTag::parse("Layer_1").unwrap_err();
+    assert_eq!(error.kind, TagErrorKind::MissingValue("Layer".to_string()));
+}
@@ -0,0 +1,73 @@
+use rnb_core::{ShapeStyle, Theme, ThemeOverride};
+
+#[test]
+fn css_only_declares_set_properties() {
+    let style = ShapeStyle::new(Some("blue"), None, Some("2"));
+    assert_eq!(style.css(), "stroke: blue !important; stroke-width: 2 !important");
+    assert_eq!(ShapeStyle::default().css(), "");
+}
+
+#[test]
+fn presets_are_found_by_name() {
+    for name in Theme::PRESETS {
+        assert_eq!(Theme::preset(name).unwrap().name, name);
+    }
+    assert_eq!(Theme::preset("sepia"), None);
+}
+
+#[test]
+fn overrides_merge_per_property() {
+    let theme_override: ThemeOverride = serde_json::from_str(r##"{"highlight": {"fill": "#00a19a"}}"##).unwrap();
+    let theme = Theme::light().with_override(&theme_override);
+    assert_eq!(theme.highlight, ShapeStyle::new(Some("red"), Some("#00a19a"), None));
+    assert_eq!(theme.focus, Theme::light().focus);
+}
+
+#[test]
+fn overrides_cannot_escape_their_rule() {
+    let theme_override: ThemeOverride = serde_json::from_str(r##"{
+        "highlight": {"stroke": "red;} body{display:none", "fill": "rgba(0, 161, 154, 0.5)", "stroke_width": "2px}"},
+        "palette": [{"stroke": "#00a19a", "fill": "url(x)"}],
+        "background": "white; }"
+    }"##).unwrap();
+    let theme = Theme::light().with_override(&theme_override);
+    assert_eq!(theme.highlight, ShapeStyle::new(Some("red"), Some("rgba(0, 161, 154, 0.5)"), None));
+    assert_eq!(theme.palette, vec![ShapeStyle::new(Some("#00a19a"), None, None)]);
+    assert_eq!(theme.background, Theme::light().background);
+    assert!(!theme.stylesheet().contains("body"));
+
+    let theme_override: ThemeOverride = serde_json::from_str(r#"{"focus_ring": {"stroke_width": "1.5px"}, "background": "black"}"#).unwrap();
+    let theme = Theme::light().with_override(&theme_override);
+    assert_eq!(theme.focus_ring.stroke_width.as_deref(), Some("1.5px"));
+    assert_eq!(theme.background, "black");
+}
+
+#[test]
+fn stylesheet_covers_every_state() {
+    let theme = Theme::light();
+    let stylesheet = theme.stylesheet();
+    assert!(stylesheet.starts_with(r#"[data-rnb-state="focus"], polygon[data-rnb-state="focus"], [data-rnb-state="focus"] polygon, "#));
+    assert!(stylesheet.contains(r#"[data-rnb-state="focus"] use { stroke: blue !important }"#));
+    assert!(stylesheet.contains(r#"[data-rnb-state="unfocus"]"#));
+    assert!(stylesheet.contains(&format!(r#"use[data-rnb-state="highlight"], [data-rnb-state="highlight"] use {{ {} }}"#, theme.highlight.css())));
+    assert!(stylesheet.contains("[data-rnb-hover]"));
+    assert!(stylesheet.contains(r#"[data-rnb-selection="5"]"#));
+    // highlight must come after focus and selection to win where they overlap
+    assert!(stylesheet.find("\"highlight\"") > stylesheet.find("\"focus\""));
+    assert!(stylesheet.find("\"highlight\"") > stylesheet.find("data-rnb-selection"));
+    assert!(stylesheet.contains(r#"[data-rnb-state="disabled"] polygon, "#));
+    assert!(stylesheet.find("\"disabled\"") > stylesheet.find("\"focus\""));
+}
+
+#[test]
+fn stylesheet_rings_the_focused_slot() {
+    let stylesheet = Theme::light().stylesheet();
+    assert!(stylesheet.contains(r#"polygon[role="button"]:focus-visible, [role="button"]:focus-visible polygon"#));
+    assert!(stylesheet.contains(r#"[role="button"]:focus-visible { outline: none }"#));
+    // the ring is drawn over any state the slot is in
+    assert!(stylesheet.find(":focus-visible") > stylesheet.find("\"highlight\""));
+
+    let theme_override: ThemeOverride = serde_json::from_str(r#"{"focus_ring": {"stroke_width": "5"}}"#).unwrap();
+    let theme = Theme::light().with_override(&theme_override);
+    assert_eq!(theme.focus_ring, ShapeStyle::new(Some("#005fcc"), None, Some("5")));
+}
@@ -0,0 +1,61 @@
+use rnb_core::{liquid::Svg, viewport::{bounds, outline, ViewBox}};
+
+const TWO_FLOORS: &str = include_str!("fixtures/two_floors.svg");
+
+#[test]
+fn reads_the_root_view_box() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    assert_eq!(ViewBox::of(&svg), Some(ViewBox { x: 0.0, y: 0.0, width: 400.0, height: 300.0 }));
+    let sized = Svg::parse(r#"<svg width="120px" height="80"></svg>"#).unwrap();
+    assert_eq!(ViewBox::of(&sized), Some(ViewBox { x: 0.0, y: 0.0, width: 120.0, height: 80.0 }));
+    assert_eq!(ViewBox::parse("0,0 0 10"), None);
+}
+
+#[test]
+fn zoom_keeps_the_anchor_in_place() {
+    let view_box = ViewBox { x: 0.0, y: 0.0, width: 400.0, height: 300.0 };
+    let zoomed = view_box.zoom(2.0, (100.0, 100.0));
+    assert_eq!(zoomed, ViewBox { x: 50.0, y: 50.0, width: 200.0, height: 150.0 });
+    assert_eq!(zoomed.zoom(0.5, (100.0, 100.0)), view_box);
+    assert_eq!(zoomed.to_string(), "50 50 200 150");
+    assert_eq!(zoomed.centered_on((0.0, 0.0)), ViewBox { x: -100.0, y: -75.0, width: 200.0, height: 150.0 });
+}
+
+#[test]
+fn maps_client_points_with_letterboxing() {
+    let view_box = ViewBox { x: 0.0, y: 0.0, width: 400.0, height: 300.0 };
+    // the height limits an 800x400 client, leaving bars left and right
+    assert_eq!(view_box.units_per_pixel((800.0, 400.0)), 0.75);
+    assert_eq!(view_box.client_to_view((400.0, 200.0), (800.0, 400.0)), (200.0, 150.0));
+    assert_eq!(view_box.client_to_view((400.0, 0.0), (800.0, 400.0)), (200.0, 0.0));
+}
+
+#[test]
+fn bounds_cover_matching_polygons() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    let floor = bounds(&svg, |element_tag| element_tag.floor_token().as_deref() == Some("floor-1"));
+    assert_eq!(floor, Some(ViewBox { x: 10.0, y: 10.0, width: 380.0, height: 280.0 }));
+    let slot = bounds(&svg, |element_tag| element_tag.slot_token().as_deref() == Some("slot-102"));
+    assert_eq!(slot, Some(ViewBox { x: 200.0, y: 10.0, width: 190.0, height: 130.0 }));
+    assert_eq!(bounds(&svg, |element_tag| element_tag.slot.as_deref() == Some("999")), None);
+}
+
+#[test]
+fn outline_lists_the_floor_polygons() {
+    let svg = Svg::parse(TWO_FLOORS).unwrap();
+    let floor = outline(&svg, |element_tag| element_tag.floor_token().as_deref() == Some("floor-2"));
+    assert_eq!(floor, ["10,10 390,10 390,290 10,290 10,10"]);
+    assert_eq!(outline(&svg, |element_tag| element_tag.floor_token().as_deref() == Some("floor-1")).len(), 3);
+}
+
+#[test]
+fn bounds_follow_any_shape_through_transforms() {
+    let svg = Svg::parse(r#"<svg viewBox="0 0 400 300">
+        <g id="floor-1" transform="translate(100 50)">
+            <rect id="slot-a_floor-1" x="10" y="20" width="30" height="40"/>
+        </g>
+    </svg>"#).unwrap();
+    let slot = bounds(&svg, |element_tag| element_tag.slot_token().as_deref() == Some("slot-a"));
+    assert_eq!(slot, Some(ViewBox { x: 110.0, y: 70.0, width: 30.0, height: 40.0 }));
+    assert_eq!(outline(&svg, |element_tag| element_tag.floor.is_some()), ["110,70 140,70 140,110 110,110"]);
+}
@@ -0,0 +1,139 @@
+use std::{collections::HashMap, fs, path::Path, sync::Mutex};
+
+use serde::{Serialize, Deserialize};
+
+// Development accounts, read from `users.toml` in the data directory:
+//
+//     [[users]]
+//     id = 1
+//     username = "editor"
+//     password = "editor"
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub id: u32,
+    pub username: String,
+    pub password: String,
+}
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Accounts {
+    #[serde(default)]
+    users: Vec<Account>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoginForm {
+    pub username: String,
+    pub password: String,
+}
+
+// Same shape as the frontend's `User`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct User {
+    pub id: u32,
+    pub username: String,
+    pub token: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Auth {
+    accounts: Vec<Account>,
+    // token -> user
+    sessions: Mutex<HashMap<String, User>>,
+}
+impl Auth {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let accounts = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str::<Accounts>(&content).map_err(|e| e.to_string())?.users,
+            Err(_) => Vec::new(),
+        };
+        Ok(Self { accounts, sessions: Mutex::new(HashMap::new()) })
+    }
+
+    pub fn login(&self, form: &LoginForm) -> Option<User> {
+        let account = self
+            .accounts
+            .iter()
+            .find(|account| account.username == form.username && account.password == form.password)?;
+        let user = User {
+            id: account.id,
+            username: account.username.clone(),
+            token: uuid::Uuid::new_v4().to_string(),
+        };
+        self.sessions.lock().unwrap().insert(user.token.clone(), user.clone());
+        Some(user)
+    }
+
+    pub fn reauth(&self, user: &User) -> bool {
+        self.sessions.lock().unwrap().get(&user.token) == Some(user)
+    }
+
+    pub fn logout(&self, user: &User) -> bool {
+        let mut sessions = self.sessions.lock().unwrap();
+        if sessions.get(&user.token) == Some(user) {
+            sessions.remove(&user.token);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn auth() -> Auth {
+        let path = env::temp_dir().join(format!("rnb-users-{}.toml", uuid::Uuid::new_v4()));
+        fs::write(&path, "[[users]]\nid = 1\nusername = \"editor\"\npassword = \"secret\"\n").unwrap();
+        let auth = Auth::load(&path).unwrap();
+        fs::remove_file(path).unwrap();
+        auth
+    }
+
+    fn form(username: &str, password: &str) -> LoginForm {
+        LoginForm { username: username.to_string(), password: password.to_string() }
+    }
+
+    #[test]
+    fn loads_accounts_and_tolerates_a_missing_file() {
+        assert_eq!(auth().accounts.len(), 1);
+        let missing = Auth::load(&env::temp_dir().join("rnb-users-missing.toml")).unwrap();
+        assert!(missing.accounts.is_empty());
+
+        let path = env::temp_dir().join(format!("rnb-users-{}.toml", uuid::Uuid::new_v4()));
+        fs::write(&path, "[[users]]\nid = \"one\"\n").unwrap();
+        assert!(Auth::load(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn login_needs_matching_credentials() {
+        let auth = auth();
+        assert_eq!(auth.login(&form("editor", "wrong")), None);
+        assert_eq!(auth.login(&form("nobody", "secret")), None);
+
+        let user = auth.login(&form("editor", "secret")).unwrap();
+        assert_eq!((user.id, user.username.as_str()), (1, "editor"));
+        let again = auth.login(&form("editor", "secret")).unwrap();
+        assert_ne!(user.token, again.token);
+    }
+
+    #[test]
+    fn reauth_accepts_only_live_sessions() {
+        let auth = auth();
+        let user = auth.login(&form("editor", "secret")).unwrap();
+        assert!(auth.reauth(&user));
+
+        let forged = User { token: "forged".to_string(), ..user.clone() };
+        assert!(!auth.reauth(&forged));
+        let renamed = User { username: "admin".to_string(), ..user.clone() };
+        assert!(!auth.reauth(&renamed));
+
+        assert!(!auth.logout(&forged));
+        assert!(auth.logout(&user));
+        assert!(!auth.reauth(&user));
+        assert!(!auth.logout(&user));
+    }
+}
@@ -0,0 +1,155 @@
+// Reference backend for offline development of the frontend.
+//
+//     RNB_DATA_DIR=rnb_server/data RNB_DIST_DIR=dist cargo run -p rnb_server
+//
+// Serves the endpoints the frontend calls plus the trunk `dist/` output, so
+// `trunk build` followed by this binary gives a complete local setup.
+
+use std::{env, path::PathBuf};
+
+use actix_files::NamedFile;
+use actix_web::{
+    get, guard, http::header, middleware::DefaultHeaders, post, web, App, HttpRequest, HttpResponse, HttpServer,
+};
+
+mod auth;
+mod store;
+
+use auth::{Auth, LoginForm, User};
+use store::{Store, StoreError};
+
+struct AppState {
+    store: Store,
+    auth: Auth,
+    dist: PathBuf,
+}
+impl AppState {
+    fn index(&self, req: &HttpRequest) -> HttpResponse {
+        match NamedFile::open(self.dist.join("index.html")) {
+            Ok(file) => file.into_response(req),
+            Err(_) => HttpResponse::NotFound().body("dist/index.html not found, run `trunk build` first"),
+        }
+    }
+}
+
+fn accepts_html(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .map(|accept| accept.contains("text/html"))
+        .unwrap_or(false)
+}
+
+#[get("/")]
+async fn index(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
+    state.index(&req)
+}
+
+// `/:code` is shared by three things: built assets in `dist/`, browser
+// navigation to the Yew `Route::Code` page, and the entity JSON the page fetches.
+#[get("/{code}")]
+async fn entity(req: HttpRequest, code: web::Path<String>, state: web::Data<AppState>) -> HttpResponse {
+    let code = code.into_inner();
+    if Store::is_valid_code(&code) {
+        let asset = state.dist.join(&code);
+        if asset.is_file() {
+            if let Ok(file) = NamedFile::open(asset) {
+                return file.into_response(&req);
+            }
+        }
+    }
+    if accepts_html(&req) {
+        return state.index(&req);
+    }
+    match state.store.entity(&code) {
+        Ok(entity) => HttpResponse::Ok().json(entity),
+        Err(StoreError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(StoreError::Io(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(StoreError::Manifest(e)) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+// Same split as `entity`: the page itself for browsers, the site JSON otherwise.
+#[get("/site/{code}")]
+async fn site(req: HttpRequest, code: web::Path<String>, state: web::Data<AppState>) -> HttpResponse {
+    if accepts_html(&req) {
+        return state.index(&req);
+    }
+    match state.store.site(&code.into_inner()) {
+        Ok(site) => HttpResponse::Ok().json(site),
+        Err(StoreError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(StoreError::Io(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(StoreError::Manifest(e)) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[post("/editor/login")]
+async fn login(form: web::Json<LoginForm>, state: web::Data<AppState>) -> HttpResponse {
+    match state.auth.login(&form) {
+        Some(user) => HttpResponse::Ok().json(user),
+        None => HttpResponse::Unauthorized().finish(),
+    }
+}
+
+#[post("/editor/reauth")]
+async fn reauth(user: web::Json<User>, state: web::Data<AppState>) -> HttpResponse {
+    if state.auth.reauth(&user) {
+        HttpResponse::Ok().finish()
+    } else {
+        HttpResponse::Unauthorized().finish()
+    }
+}
+
+#[post("/editor/logout")]
+async fn logout(user: web::Json<User>, state: web::Data<AppState>) -> HttpResponse {
+    if state.auth.logout(&user) {
+        HttpResponse::Ok().finish()
+    } else {
+        HttpResponse::Unauthorized().finish()
+    }
+}
+
+// CORS preflight; trunk serves the frontend from a different origin.
+async fn preflight() -> HttpResponse {
+    HttpResponse::NoContent()
+        .insert_header((header::ACCESS_CONTROL_ALLOW_METHODS, "GET, POST, OPTIONS"))
+        .insert_header((header::ACCESS_CONTROL_ALLOW_HEADERS, "content-type"))
+        .finish()
+}
+
+async fn fallback(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
+    if req.method() == actix_web::http::Method::GET {
+        state.index(&req)
+    } else {
+        HttpResponse::NotFound().finish()
+    }
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let bind = env::var("RNB_BIND").unwrap_or("127.0.0.2:8081".to_string());
+    let data = PathBuf::from(env::var("RNB_DATA_DIR").unwrap_or("rnb_server/data".to_string()));
+    let dist = PathBuf::from(env::var("RNB_DIST_DIR").unwrap_or("dist".to_string()));
+
+    let auth = Auth::load(&data.join("users.toml"))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let state = web::Data::new(AppState { store: Store::new(&data), auth, dist });
+
+    println!("serving {} on http://{}", data.display(), bind);
+    HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .wrap(DefaultHeaders::new().add((header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")))
+            .service(web::resource("/{tail:.*}").guard(guard::Options()).to(preflight))
+            .service(index)
+            .service(login)
+            .service(reauth)
+            .service(logout)
+            .service(site)
+            .service(entity)
+            .default_service(web::to(fallback))
+    })
+    .bind(bind)?
+    .run()
+    .await
+}
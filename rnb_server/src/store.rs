@@ -0,0 +1,257 @@
+use std::{fs, io, path::{Path, PathBuf}};
+
+use rnb_core::{EntityResponse, FloorOverride, Placement, SiteBuilding, SiteResponse, ThemeOverride};
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
+
+// `<code>.toml` or `<code>.json` next to the drawing it describes:
+//
+//     name = "North Wing"
+//     default_floor = "floor-1"
+//     svg = "north-wing.svg"    # optional, defaults to `<code>.svg`
+//     units_per_metre = 50.0    # optional, areas are in drawing units without it
+//
+//     [theme.highlight]         # optional, see `rnb_core::ThemeOverride`
+//     fill = "#00a19a"
+//
+//     [[floors]]                # optional, see `rnb_core::FloorOverride`
+//     token = "floor-b1"
+//     name = "Car park"
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub name: String,
+    pub default_floor: String,
+    pub svg: Option<String>,
+    #[serde(default)]
+    pub theme: Option<ThemeOverride>,
+    #[serde(default)]
+    pub units_per_metre: Option<f64>,
+    #[serde(default)]
+    pub floors: Vec<FloorOverride>,
+}
+
+// `sites/<code>.toml` (or `.json`), placing entities of the same store:
+//
+//     name = "North Campus"
+//
+//     [[buildings]]
+//     code = "north-wing"
+//     x = 0.0
+//     y = 0.0
+//
+//     [[buildings]]
+//     code = "south-wing"
+//     x = 600.0
+//     rotation = 90.0           # degrees, about the drawing's origin
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SiteManifest {
+    pub name: String,
+    pub buildings: Vec<Placement>,
+}
+
+#[derive(Debug)]
+pub enum StoreError {
+    NotFound,
+    Io(io::Error),
+    Manifest(String),
+}
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => StoreError::NotFound,
+            _ => StoreError::Io(e),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Store {
+    root: PathBuf,
+}
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // Codes come straight from the URL, so anything that could leave `root` is
+    // treated as unknown.
+    pub fn is_valid_code(code: &str) -> bool {
+        !code.is_empty()
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !code.starts_with('.')
+    }
+
+    fn manifest<T: DeserializeOwned>(&self, dir: &Path, code: &str) -> Result<T, StoreError> {
+        let toml_path = dir.join(format!("{}.toml", code));
+        if toml_path.is_file() {
+            let content = fs::read_to_string(toml_path)?;
+            return toml::from_str(&content).map_err(|e| StoreError::Manifest(e.to_string()));
+        }
+        let content = fs::read_to_string(dir.join(format!("{}.json", code)))?;
+        serde_json::from_str(&content).map_err(|e| StoreError::Manifest(e.to_string()))
+    }
+
+    pub fn entity(&self, code: &str) -> Result<EntityResponse, StoreError> {
+        if !Self::is_valid_code(code) {
+            return Err(StoreError::NotFound);
+        }
+        let manifest: Manifest = self.manifest(&self.root, code)?;
+        let svg_file = manifest.svg.clone().unwrap_or(format!("{}.svg", code));
+        if Path::new(&svg_file).components().count() != 1 {
+            return Err(StoreError::Manifest(format!("svg \"{}\" must be a file name", svg_file)));
+        }
+        let svg_raw_content = fs::read_to_string(self.root.join(svg_file))?;
+        Ok(EntityResponse {
+            name: manifest.name,
+            svg_raw_content: Some(svg_raw_content),
+            svg_content: None,
+            default_floor: manifest.default_floor,
+            theme: manifest.theme,
+            units_per_metre: manifest.units_per_metre,
+            floors: manifest.floors,
+        })
+    }
+
+    pub fn site(&self, code: &str) -> Result<SiteResponse, StoreError> {
+        if !Self::is_valid_code(code) {
+            return Err(StoreError::NotFound);
+        }
+        let manifest: SiteManifest = self.manifest(&self.root.join("sites"), code)?;
+        let buildings = manifest
+            .buildings
+            .into_iter()
+            .map(|placement| {
+                // a building the site names but the store lacks is a broken site, not a missing one
+                let entity = self.entity(&placement.code).map_err(|e| match e {
+                    StoreError::NotFound => StoreError::Manifest(format!("building \"{}\" not found", placement.code)),
+                    e => e,
+                })?;
+                Ok(SiteBuilding { placement, entity })
+            })
+            .collect::<Result<Vec<SiteBuilding>, StoreError>>()?;
+        Ok(SiteResponse { name: manifest.name, buildings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    // A fresh data directory holding `files`, relative to its root.
+    fn data_dir(files: &[(&str, &str)]) -> PathBuf {
+        let root = env::temp_dir().join(format!("rnb-store-{}", uuid::Uuid::new_v4()));
+        for (name, content) in files {
+            let path = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    const SVG: &str = r#"<svg><g id="floor-1"><rect id="slot-a_floor-1"/></g></svg>"#;
+
+    #[test]
+    fn codes_that_could_leave_the_root_are_invalid() {
+        for code in ["north-wing", "north_wing.v2", "B1"] {
+            assert!(Store::is_valid_code(code), "{}", code);
+        }
+        for code in ["", "..", "../secret", "..\\secret", "a/b", "/etc/passwd", ".hidden", "%2e%2e%2fsecret", "..%2Fsecret", "%2E%2E", "a%00"] {
+            assert!(!Store::is_valid_code(code), "{}", code);
+        }
+    }
+
+    #[test]
+    fn traversal_is_not_found_even_when_the_target_exists() {
+        let parent = data_dir(&[("secret.toml", "name = \"Secret\"\ndefault_floor = \"floor-1\"\n"), ("secret.svg", SVG)]);
+        let store = Store::new(parent.join("data"));
+        fs::create_dir_all(parent.join("data")).unwrap();
+        assert!(matches!(store.entity("../secret"), Err(StoreError::NotFound)));
+        assert!(matches!(store.entity("..%2Fsecret"), Err(StoreError::NotFound)));
+        fs::remove_dir_all(parent).unwrap();
+    }
+
+    #[test]
+    fn loads_toml_then_json_manifests() {
+        let root = data_dir(&[
+            ("north.toml", "name = \"North\"\ndefault_floor = \"floor-1\"\n"),
+            ("north.svg", SVG),
+            ("south.json", r#"{"name": "South", "default_floor": "floor-1", "svg": "drawing.svg"}"#),
+            ("drawing.svg", SVG),
+        ]);
+        let store = Store::new(&root);
+
+        let north = store.entity("north").unwrap();
+        assert_eq!(north.name, "North");
+        assert_eq!(north.svg_raw_content.as_deref(), Some(SVG));
+
+        let south = store.entity("south").unwrap();
+        assert_eq!((south.name.as_str(), south.svg_raw_content.as_deref()), ("South", Some(SVG)));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reports_missing_and_broken_manifests() {
+        let root = data_dir(&[
+            ("broken.toml", "name = "),
+            ("escape.toml", "name = \"Escape\"\ndefault_floor = \"floor-1\"\nsvg = \"../escape.svg\"\n"),
+            ("drawingless.toml", "name = \"Drawingless\"\ndefault_floor = \"floor-1\"\n"),
+        ]);
+        let store = Store::new(&root);
+        assert!(matches!(store.entity("missing"), Err(StoreError::NotFound)));
+        assert!(matches!(store.entity("broken"), Err(StoreError::Manifest(_))));
+        assert!(matches!(store.entity("escape"), Err(StoreError::Manifest(_))));
+        assert!(matches!(store.entity("drawingless"), Err(StoreError::NotFound)));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reads_the_drawing_scale() {
+        let root = data_dir(&[
+            ("north.toml", "name = \"North\"\ndefault_floor = \"floor-1\"\nunits_per_metre = 50.0\n"),
+            ("north.svg", SVG),
+            ("south.json", r#"{"name": "South", "default_floor": "floor-1", "svg": "north.svg"}"#),
+        ]);
+        let store = Store::new(&root);
+        assert_eq!(store.entity("north").unwrap().units_per_metre, Some(50.0));
+        assert_eq!(store.entity("south").unwrap().units_per_metre, None);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reads_floor_overrides() {
+        let root = data_dir(&[
+            ("north.toml", "name = \"North\"\ndefault_floor = \"floor-1\"\n\n[[floors]]\ntoken = \"floor-1\"\nname = \"Lobby\"\nelevation = 3.5\n"),
+            ("north.svg", SVG),
+        ]);
+        let store = Store::new(&root);
+        let floors = store.entity("north").unwrap().floors;
+        assert_eq!(floors.len(), 1);
+        assert_eq!((floors[0].token.as_str(), floors[0].name.as_deref(), floors[0].elevation), ("floor-1", Some("Lobby"), Some(3.5)));
+        assert_eq!(floors[0].level, None);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn sites_load_their_buildings() {
+        let root = data_dir(&[
+            ("north.toml", "name = \"North\"\ndefault_floor = \"floor-1\"\n"),
+            ("north.svg", SVG),
+            ("sites/campus.toml", "name = \"Campus\"\n\n[[buildings]]\ncode = \"north\"\nx = 10.0\nrotation = 90.0\n"),
+            ("sites/broken.toml", "name = \"Broken\"\n\n[[buildings]]\ncode = \"gone\"\n"),
+        ]);
+        let store = Store::new(&root);
+
+        let campus = store.site("campus").unwrap();
+        assert_eq!(campus.name, "Campus");
+        assert_eq!(campus.buildings.len(), 1);
+        assert_eq!(campus.buildings[0].placement, Placement { code: "north".to_string(), x: 10.0, y: 0.0, rotation: 90.0 });
+        assert_eq!(campus.buildings[0].entity.name, "North");
+
+        // sites and entities do not share codes
+        assert!(matches!(store.site("north"), Err(StoreError::NotFound)));
+        assert!(matches!(store.site("broken"), Err(StoreError::Manifest(message)) if message.contains("gone")));
+        assert!(matches!(store.site("../north"), Err(StoreError::NotFound)));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
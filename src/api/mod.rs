@@ -0,0 +1,114 @@
+use std::fmt;
+
+use gloo_utils::document;
+use reqwasm::http::{Request, Response};
+use serde::de::DeserializeOwned;
+use web_sys::{js_sys, AbortSignal};
+
+use rnb_core::SiteResponse;
+
+use crate::{EntityResponse, LoginForm, User};
+
+const DEFAULT_BASE_URL: &str = "http://127.0.0.2:8081";
+const BASE_URL_META: &str = "rnb-api-base";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiError {
+    // the request never got a response (offline, CORS, DNS...)
+    Network(String),
+    NotFound,
+    Unauthorized,
+    Status(u16, String),
+    Decode(String),
+}
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(e) => write!(f, "network error: {}", e),
+            ApiError::NotFound => write!(f, "not found"),
+            ApiError::Unauthorized => write!(f, "unauthorized"),
+            ApiError::Status(status, text) => write!(f, "server responded {} {}", status, text),
+            ApiError::Decode(e) => write!(f, "unexpected response: {}", e),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiClient {
+    base_url: String,
+}
+impl ApiClient {
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string() }
+    }
+
+    // `<meta name="rnb-api-base" content="...">` in index.html wins over the
+    // `RNB_API_BASE` value baked in at build time.
+    pub fn from_document() -> Self {
+        let meta = document()
+            .query_selector(&format!("meta[name=\"{}\"]", BASE_URL_META))
+            .ok()
+            .flatten()
+            .and_then(|meta| meta.get_attribute("content"))
+            .filter(|content| !content.trim().is_empty());
+        match meta {
+            Some(base_url) => Self::new(&base_url),
+            None => Self::new(option_env!("RNB_API_BASE").unwrap_or(DEFAULT_BASE_URL)),
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    pub async fn fetch_entity(&self, code: &str, signal: Option<&AbortSignal>) -> Result<EntityResponse, ApiError> {
+        let code: String = js_sys::encode_uri_component(code).into();
+        let response = send(Request::get(&self.url(&code)).abort_signal(signal)).await?;
+        decode(response).await
+    }
+
+    pub async fn fetch_site(&self, code: &str, signal: Option<&AbortSignal>) -> Result<SiteResponse, ApiError> {
+        let code: String = js_sys::encode_uri_component(code).into();
+        let response = send(Request::get(&self.url(&format!("site/{}", code))).abort_signal(signal)).await?;
+        decode(response).await
+    }
+
+    pub async fn login(&self, form: &LoginForm) -> Result<User, ApiError> {
+        let body = serde_json::to_string(form).map_err(|e| ApiError::Decode(e.to_string()))?;
+        let response = send(post_json(&self.url("editor/login"), body)).await?;
+        decode(response).await
+    }
+
+    pub async fn reauth(&self, user: &User) -> Result<(), ApiError> {
+        let body = serde_json::to_string(user).map_err(|e| ApiError::Decode(e.to_string()))?;
+        send(post_json(&self.url("editor/reauth"), body)).await?;
+        Ok(())
+    }
+
+    pub async fn logout(&self, user: &User) -> Result<(), ApiError> {
+        let body = serde_json::to_string(user).map_err(|e| ApiError::Decode(e.to_string()))?;
+        send(post_json(&self.url("editor/logout"), body)).await?;
+        Ok(())
+    }
+}
+
+fn post_json(url: &str, body: String) -> Request {
+    Request::post(url)
+        .header("content-type", "application/json")
+        .body(body)
+}
+
+async fn send(request: Request) -> Result<Response, ApiError> {
+    let response = request.send().await.map_err(|e| ApiError::Network(e.to_string()))?;
+    match response.status() {
+        200..=299 => Ok(response),
+        401 | 403 => Err(ApiError::Unauthorized),
+        404 => Err(ApiError::NotFound),
+        status => Err(ApiError::Status(status, response.status_text())),
+    }
+}
+
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    let body = response.text().await.map_err(|e| ApiError::Network(e.to_string()))?;
+    serde_json::from_str(&body).map_err(|e| ApiError::Decode(e.to_string()))
+}
@@ -1,15 +0,0 @@
-
-struct Svg {
-
-}
-struct G {
-    
-}
-struct Polygon {
-
-}
-enum Property {
-    Id(String)
-    Points(String),
-    
-}
\ No newline at end of file
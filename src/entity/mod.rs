@@ -2,12 +2,14 @@ use std::{borrow::{Borrow, BorrowMut}, cell::RefCell, collections::{HashMap, Has
 
 use gloo::console::log as clog;
 use gloo_utils::document;
-use regex::Regex;
 use serde::{Serialize, Deserialize};
 use web_sys::Element;
 use yew::{use_context, Properties, Reducible};
 
-use crate::{_SvgContent::svg, entity, theme::Focus, BuildNestedElement, EntityContext, NestedElement};
+use crate::{_SvgContent::svg, entity, theme::{Theme, ThemeOverride}, EntityContext};
+
+pub use rnb_core::{EntityResponse, ElementKey, ElementTag};
+use rnb_core::{floor::{self, Floor}, FloorOverride};
 
 #[derive(Debug)]
 pub enum EntityCase {
@@ -16,16 +18,22 @@ pub enum EntityCase {
 
     Init(Option<Entity>),
     Highlight(String),
+    // back to no highlighted slot, keeping the multi-selection
+    ClearHighlight,
+    // element indices of a clicked shape and its ancestors, innermost first
+    Select(Vec<usize>),
+    // same, adding the slot to the multi-selection or taking it out again
+    SelectMore(Vec<usize>),
+    // slot token on the current floor
+    ToggleSelect(String),
+    ClearSelection,
     ProduceOption(Option<String>),
+    // preset picked by the user, the entity's own override still applies on top
+    Theme(Theme),
+    // slot names drawn over the plan
+    Labels(bool),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct EntityResponse {
-    pub name: String,
-    pub svg_raw_content: Option<String>,
-    pub svg_content: Option<String>,
-    pub default_floor: String,
-}
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Properties)]
 pub struct SvgContentt {
     pub svg_content: Option<String>
@@ -49,9 +57,9 @@ pub struct OptionY {
 }
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Properties)]
 pub struct ElementData {
-    pub data: Option<HashMap<(String, String, Range<i32>), bool>>,
+    pub data: Option<HashMap<ElementKey, bool>>,
 }
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Properties)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Properties)]
 pub struct Entity {
     pub name: RefCell<String>,
     pub svg_raw_content: RefCell<Option<String>>,
@@ -68,6 +76,14 @@ pub struct Entity {
     
     pub element: RefCell<ElementData>,
     pub data_name: Option<HashMap<String, bool>>,
+
+    // (slot token, floor token) in selection order
+    pub selected: Vec<(String, String)>,
+    pub theme: Theme,
+    pub theme_override: Option<ThemeOverride>,
+    pub units_per_metre: Option<f64>,
+    pub show_labels: bool,
+    pub floor_overrides: Vec<FloorOverride>,
 }
 impl Reducible for Entity {
     type Action = EntityCase;
@@ -75,10 +91,17 @@ impl Reducible for Entity {
         match action {
             EntityCase::Init(init) => {
                 match init {
-                    Some(s) => {
+                    Some(mut s) => {
+                        s.theme = self.theme.clone();
+                        s.show_labels = self.show_labels;
                         return s.into()
                     },
-                    None => return Entity::new().into()
+                    None => {
+                        let mut s = Entity::new();
+                        s.theme = self.theme.clone();
+                        s.show_labels = self.show_labels;
+                        return s.into()
+                    }
                 }
             },
             EntityCase::Highlight(highlight) => {
@@ -95,8 +118,61 @@ impl Reducible for Entity {
                     }
                 }
             },
+            EntityCase::ClearHighlight => {
+                Entity::mutate_option(&mut self).x = None;
+                self.remark();
+            },
+            EntityCase::Select(indices) => {
+                match self.slot_at(&indices) {
+                    Some(slot) => return self.reduce(EntityCase::Highlight(slot)),
+                    None => clog!("no slot on the current floor at the clicked shape"),
+                }
+            },
+            EntityCase::SelectMore(indices) => {
+                match self.slot_at(&indices) {
+                    Some(slot) => return self.reduce(EntityCase::ToggleSelect(slot)),
+                    None => clog!("no slot on the current floor at the clicked shape"),
+                }
+            },
+            EntityCase::ToggleSelect(slot) => {
+                // disabled slots are left out of the current floor's slots
+                if !self.floor_slots().contains(&slot) {
+                    clog!("slot cannot be picked");
+                    return self;
+                }
+                let key = (slot, self.current_floor());
+                let limit = self.theme().palette.len();
+                let this = Rc::make_mut(&mut self);
+                match this.selected.iter().position(|selected| *selected == key) {
+                    Some(position) => {
+                        this.selected.remove(position);
+                    },
+                    None if this.selected.len() < limit => this.selected.push(key),
+                    None => {
+                        clog!(format!("at most {} slots can be selected at once", limit));
+                        return self;
+                    }
+                }
+                self.remark();
+            },
+            EntityCase::ClearSelection => {
+                Rc::make_mut(&mut self).selected.clear();
+                self.remark();
+            },
+            EntityCase::Theme(theme) => {
+                Rc::make_mut(&mut self).theme = theme;
+            },
+            EntityCase::Labels(show_labels) => {
+                Rc::make_mut(&mut self).show_labels = show_labels;
+            },
             EntityCase::ProduceOption(floor_str) => {
-                let new = self.clone().produce_option(floor_str.clone()).unwrap();
+                let new = match self.clone().produce_option(floor_str.clone()) {
+                    Ok(new) => new,
+                    Err(e) => {
+                        clog!(e);
+                        return self;
+                    }
+                };
                 let new_string = new.0;
                 let new_option_x = new.1;
                 let new_option_y = new.2;
@@ -109,6 +185,16 @@ impl Reducible for Entity {
                 svg_content.3.data = Some(new_slots);
                 svg_content.4.y = floor_str;
                 svg_content.5.svg_content = None;
+                drop(svg_content);
+                // a slot left behind on the old floor is no longer highlighted
+                let slot = self.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+                let kept = slot.as_ref().is_some_and(|slot| self.floor_slots().contains(slot));
+                if slot.is_some() && !kept {
+                    Entity::mutate_option(&mut self).x = None;
+                }
+                if kept || !self.selected.is_empty() {
+                    self.remark();
+                }
             },
         } 
         self
@@ -171,6 +257,12 @@ impl Entity {
             focus_option: None,
             element: ElementData {data: None}.into(),
             data_name: None,
+            selected: Vec::new(),
+            theme: Theme::default(),
+            theme_override: entity_response.theme,
+            units_per_metre: entity_response.units_per_metre,
+            show_labels: false,
+            floor_overrides: entity_response.floors,
         }
     }
     pub fn new() -> Self {
@@ -187,271 +279,155 @@ impl Entity {
             focus_option: None,
             element: ElementData {data: None}.into(),
             data_name: None,
+            selected: Vec::new(),
+            theme: Theme::default(),
+            theme_override: None,
+            units_per_metre: None,
+            show_labels: false,
+            floor_overrides: Vec::new(),
         }
     }
-    fn process_string(input: &str) -> String {
-        let re = Regex::new(r"-(\d+)(?:(_[^_]+)?|-[^-_]+)").unwrap();
-        let mut found_number = false;
-        
-        re.replace_all(input, |caps: &regex::Captures| {
-            if let Some(_) = caps.get(1) {
-                if !found_number {
-                    found_number = true;
-                    format!("-{}", &caps[1])  
-                } else {
-                    "".to_string()
-                }
-            } else if let Some(_) = caps.get(2) {
-                "-_".to_string() 
-            } else {
-                "".to_string() 
-            }
-        }).to_string()
+    // the user's preset with the entity's override applied
+    pub fn theme(&self) -> Theme {
+        match &self.theme_override {
+            Some(theme_override) => self.theme.with_override(theme_override),
+            None => self.theme.clone(),
+        }
     }
-    pub fn produce_option(& self, floor: Option<String>) -> Result<(String, HashMap<String, String>, HashMap<String, String>, HashMap<(String, String, Range<i32>), bool>), &'static str> {
-        let style =  Regex::new(r#"style="([^"]*)""#).unwrap();
-        let _g_tag = Regex::new(r#"<g\b[^>]*>(.*?)<\/g>|<polygon\b[^>]*>(.*?)<\/polygon>|<g\b[^>]*\/>|<polygon\b[^>]*\/>"#).unwrap();
-        let _shape_tag = Regex::new(r#"<(polygon|rect|path)\b[^>]*>(.*?)"#).unwrap();
-        let _class_value = Regex::new(r#"class="([^"]+)""#).unwrap();
-        
-        let data_name_property = Regex::new(r#"id="([^"]+)""#).unwrap();
-        
-        let focus_style = r#"style="stroke: blue !important""#;
-        let unfocus_style = r#"style="stroke: none !important; fill: none !important""#;
-
-        let mut x: HashMap<String, String> = HashMap::new();
-        let mut y: HashMap<String, String> = HashMap::new();
-        
-        // (id, data-name, style-range)
-        let mut element: HashMap<(String, String, Range<i32>), bool> = HashMap::new();
-        element.extend(self.element.borrow().data.clone().unwrap_or(HashMap::new()));
+    pub fn produce_option(& self, floor: Option<String>) -> Result<(String, HashMap<String, String>, HashMap<String, String>, HashMap<ElementKey, bool>), &'static str> {
+        let element: HashMap<ElementKey, bool> = self.element.borrow().data.clone().unwrap_or(HashMap::new());
 
-        let mut ranges: Vec<Range<i32>> = Vec::new();
-        let mut to_focus_ranges: Vec<Range<i32>> = Vec::new();
-        
-        //let mut svg_raw_content = self.svg_raw_content.borrow().clone();
-        let mut content: Option<String> = if let Some(svg_content_data) = self.svg_content.borrow().clone().svg_content {
+        let content: Option<String> = if let Some(svg_content_data) = self.svg_content.borrow().clone().svg_content {
             Some(svg_content_data)
         } else if let Some(svg_raw_content) = self.svg_raw_content.borrow().clone() {
             Some(svg_raw_content)
         } else {
             None
         };
-        let current_option = self.current_option.borrow().clone();
 
         let floor: String = floor.clone().unwrap_or("".to_string());
 
         let floor_ref: &str = floor.as_str();
         
-        let floor = if &floor_ref != &"" {
-            &floor_ref
-        } else if &self.default_floor.as_str() != &"" {
-            &self.default_floor.as_str()
+        let floor: &str = if !floor_ref.is_empty() {
+            floor_ref
         } else {
-            ""
+            self.default_floor.as_str()
         };
 
+        let content = match content {
+            Some(content) => content,
+            None => return Ok(("".to_string(), HashMap::new(), HashMap::new(), element)),
+        };
+        let produced = rnb_core::produce_option(&content, floor, element)?;
+        for e in &produced.errors {
+            clog!(e.to_string());
+        }
 
-        if let Some(svg_raw_content) = &content {
-            for some_data_name_property in data_name_property.captures_iter(&svg_raw_content) {
-                if let (Some(data_name_property), Some(data_name_value)) = (some_data_name_property.get(0), some_data_name_property.get(1)) {
-                    
-                    let raw_range = some_data_name_property.get(0).unwrap();
-                    let start = raw_range.start() as i32;
-                    let end = raw_range.end() as i32;
-
-                    let data_name_properties_vec = some_data_name_property
-                    .get(1)
-                    .unwrap()
-                    .as_str(); 
-                    
-                    let processed_string = entity::Entity::process_string(data_name_properties_vec);
+        Ok((produced.svg_content, produced.x, produced.y, produced.element))
+    }
 
-                    let data_name_properties = processed_string
-                    .split('_') 
-                    .collect::<Vec<&str>>();
-                    
+    pub fn current_floor(&self) -> String {
+        match &self.current_option.borrow().y {
+            Some(y) if !y.is_empty() => y.clone(),
+            _ => self.default_floor.clone(),
+        }
+    }
 
-                    if data_name_properties.contains(&floor) {
-                        to_focus_ranges.push(start..end);
-                    };
+    // First slot token indexed at one of `indices` whose element also sits on
+    // the current floor.
+    pub fn slot_at(&self, indices: &[usize]) -> Option<String> {
+        let element = self.element.borrow();
+        let data = element.data.as_ref()?;
+        let floor = self.current_floor();
+        indices.iter().find_map(|index| {
+            data.keys()
+                .filter(|(_, token, element_index)| element_index == index && token.starts_with("slot-"))
+                .find(|(id, _, element_index)| data.contains_key(&(id.clone(), floor.clone(), *element_index)))
+                .map(|(_, token, _)| token.clone())
+        })
+    }
 
-                    ranges.push(start..end);
+    // The drawing's floors, bottom first.
+    pub fn floors(&self) -> Vec<Floor> {
+        let tokens: Vec<String> = match &self.y_option.borrow().data {
+            Some(map) => map.keys().cloned().collect(),
+            None => Vec::new(),
+        };
+        floor::ordered(&tokens, &self.floor_overrides)
+    }
 
-                    for data_name_property in &data_name_properties {
-                        element.insert((data_name_value.as_str().to_string(), data_name_property.to_string(), start..end), true);
-                    }
+    pub fn floor(&self, token: &str) -> Floor {
+        let floor = Floor::from_token(token);
+        match self.floor_overrides.iter().find(|floor_override| floor_override.token == token) {
+            Some(floor_override) => floor.with_override(floor_override),
+            None => floor,
+        }
+    }
 
-                    let equal_floor: bool = data_name_properties.iter().any(|data_name_value| {
-                        data_name_value == &floor
-                    });
+    // Slot tokens on the current floor, sorted.
+    pub fn floor_slots(&self) -> Vec<String> {
+        let floor = self.current_floor();
+        let mut slots: Vec<String> = match &self.x_option.borrow().data {
+            Some(map) => map.iter().filter(|(_, slot_floor)| **slot_floor == floor).map(|(slot, _)| slot.clone()).collect(),
+            None => Vec::new(),
+        };
+        slots.sort();
+        slots
+    }
 
-                    for data_name_value in data_name_properties.iter() {
-                        if data_name_value.contains("floor-") {
-                            y.insert(data_name_value.to_string(), data_name_value.to_string());
-                        }
-                    }
+    // Every (slot, floor) pair in the drawing, whichever floor is showing.
+    pub fn slots(&self) -> Vec<(String, String)> {
+        let element = self.element.borrow();
+        let mut slots: Vec<(String, String)> = match element.data.as_ref() {
+            Some(data) => data.keys()
+                .filter_map(|(id, _, _)| ElementTag::parse(id).ok())
+                .filter_map(|element_tag| Some((element_tag.slot_token()?, element_tag.floor_token()?)))
+                .collect(),
+            None => Vec::new(),
+        };
+        slots.sort();
+        slots.dedup();
+        slots
+    }
 
-                    if equal_floor {
-                        for data_name_value in data_name_properties.iter() {
-                            x.insert(data_name_value.to_string(), floor.to_string());
-                            //match (self.default_floor.clone(), &floor_ref) {
-                            //    (_, floor) if !floor.is_empty() => {
-                            //    },
-                            //    (default_floor_, floor) if !floor.is_empty() => {
-                            //        x.insert(data_name_value.to_string(), default_floor_.to_string());
-                            //    },
-                            //    (default_floor_, _) => {
-                            //        x.insert(data_name_value.to_string(), default_floor_.to_string());
-                            //    }
-//
-                            //}
-                        }
-                    } 
-                }
-            }
-        }
-        
-        {
-            let mut unique_ranges = HashSet::new();
-            let mut unique_ranges_vec: Vec<Range<i32>> = ranges
-                .clone()
-                .into_iter()
-                .filter(|range| unique_ranges.insert((range.start, range.end)))
-                .collect();
-            unique_ranges_vec.sort_by(|a, b| b.start.cmp(&a.start));
-
-            let mut to_focus_unique_ranges = HashSet::new();
-            let to_focus_unique_ranges_vec: Vec<Range<i32>> = to_focus_ranges
-                .clone()
-                .into_iter()
-                .filter(|range| to_focus_unique_ranges.insert((range.start, range.end)))
-                .collect();
-            
-            
-            let mut sorted_elements: Vec<_> = element.clone().into_iter().collect();
-            sorted_elements.sort_by(|a, b| a.0 .2.end.cmp(&b.0 .2.end));
-            if let Some(ref mut svg_raw_content) = content {
-                for unique_ranges_vece in &unique_ranges_vec {
-                    let closing_bracket: Option<usize> = svg_raw_content.chars().skip(unique_ranges_vece.end as usize).position(|c| c == '>').map(|pos| pos + unique_ranges_vece.end as usize);
-                    if let Some(closing_bracket) = closing_bracket {
-                        if unique_ranges_vece.end as usize <= closing_bracket && closing_bracket <= svg_raw_content.len() {
-                            let substring = &svg_raw_content[unique_ranges_vece.end as usize..closing_bracket];
-                            if let Some(pos) = style.captures(substring) {
-                                if let Some(pos) = pos.get(0) {
-                                    let replace_start = unique_ranges_vece.end as usize + pos.start();
-                                    let replace_end = replace_start + focus_style.len();
-                                    
-                                    if to_focus_unique_ranges_vec.iter().any(|to_range| {
-                                        to_range.end == unique_ranges_vece.end
-                                    }){
-                                        svg_raw_content.replace_range(replace_start..replace_end, focus_style);
-                                    } else {
-                                        svg_raw_content.replace_range(replace_start..replace_end, unfocus_style);
-                                    }
-                                }
-                            } else {
-                                if to_focus_unique_ranges_vec.iter().any(|to_range| {
-                                    to_range.end == unique_ranges_vece.end
-                                })
-                                {
-                                    svg_raw_content.insert_str((unique_ranges_vece.end).try_into().unwrap(), focus_style);
-                                } else {
-                                    svg_raw_content.insert_str((unique_ranges_vece.end).try_into().unwrap(), unfocus_style);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Ok((content.clone().unwrap_or("".to_string()), x.to_owned(), y.to_owned(), element.to_owned()))
+    // Parsed ids of every element carrying `slot` on `floor`.
+    pub fn slot_tags(&self, slot: &str, floor: &str) -> Vec<ElementTag> {
+        let element = self.element.borrow();
+        let data = match element.data.as_ref() {
+            Some(data) => data,
+            None => return Vec::new(),
+        };
+        let mut ids: Vec<&String> = data.keys()
+            .filter(|(id, token, index)| token == slot && data.contains_key(&(id.clone(), floor.to_string(), *index)))
+            .map(|(id, _, _)| id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids.into_iter().filter_map(|id| ElementTag::parse(id).ok()).collect()
     }
 
     pub fn highlight_option(& self, slot: Option<&str>) -> Result<String, &'static str> {
 
         if slot.is_none() { return Err("nothing to process") }
         else if slot.unwrap() == self.focus_option.clone().unwrap_or("".to_string()) { return Err("nothing to process") }
-        
-        
-        let g_tag = Regex::new(r#"<g\b[^>]*>(.*?)<\/g>|<polygon\b[^>]*>(.*?)<\/polygon>|<g\b[^>]*\/>|<polygon\b[^>]*\/>"#).unwrap();
-        let shape_tag = Regex::new(r#"<(polygon)\b[^>]*>(.*?)"#).unwrap();
-        let data_name_property = Regex::new(r#"data-name="([^"]+)""#).unwrap();
-        
-        let focus_style = r#"style="stroke: #000000 !important""#;
 
-        let mut nest: BuildNestedElement = BuildNestedElement {
-            nests: Vec::new(),
-            svg_content: String::new()
-        };
-        let current_y = self.current_option.borrow().y.clone();
-        
-        let floor_scope = if let Some(current_y) = current_y {
-            current_y
-        } else {
-            self.default_floor.clone()
-        };
-        
-        let svg_content = &self.svg_content.borrow().svg_content;
-        let mut svg_content_clone = String::new();
-        if let Some(svg_content) = svg_content {
-            let capture: Vec<usize> = svg_content.match_indices(focus_style).map(|(index, _)| index + focus_style.len()).collect();
-            svg_content_clone = svg_content.clone().to_string();
-            nest.svg_content = svg_content.clone().to_string();
-            for some_g_tag_element in g_tag.captures_iter(&svg_content) {
-                if let Some(g_tag_element) = some_g_tag_element.get(0) {
-                    let g_tag_element_start = g_tag_element.start();
-                    let g_tag_element_end = g_tag_element.end();
-                    let g_tag_element_value = g_tag_element.as_str();
-                    for some_data_name_property in data_name_property.captures_iter(&g_tag_element.as_str()) {
-                        if let Some(data_name_property_value) = some_data_name_property.get(1) {
-
-                            let data_name_properties = some_data_name_property
-                            .get(1)
-                            .unwrap()
-                            .as_str()
-                            .split(' ')
-                            .collect::<Vec<&str>>();
-                            
-                            let equal_slot: bool = if data_name_properties.contains(&slot.unwrap_or("")) &&
-                            data_name_properties.contains(&floor_scope.as_str()) {
-                                true
-                            } else {
-                                false
-                            };
-
-                            if equal_slot {
-                                for some_shape_tag in shape_tag.captures_iter(&g_tag_element.as_str()) {
-                                    if let (Some(polygon_element), Some(shape_element_tag_name)) = (some_shape_tag.get(0), some_shape_tag.get(1)) {
-                                        let polygon_element_start = polygon_element.start();
-                                        let polygon_element_end =   polygon_element.end();
-                                        let polygon_element_value = polygon_element.as_str();
-
-                                        let shape_element_tag_name_value = shape_element_tag_name.as_str();
-
-                                        nest.nests.push(NestedElement {
-                                            polygon_element_start,
-                                            polygon_element_end,
-                                            polygon_element_value: polygon_element_value.to_owned(),
-                                            
-                                            g_tag_element_start,
-                                            g_tag_element_end,
-                                            g_tag_element_value: g_tag_element_value.to_owned(),
-                                            
-                                            shape_element_tag_name_value: shape_element_tag_name_value.to_owned(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        } else {
+        match &self.svg_content.borrow().svg_content {
+            Some(svg_content) => rnb_core::mark(svg_content, slot, &self.selected, &self.current_floor()),
+            None => Ok(String::new()),
+        }
+    }
 
+    // Marks the selection again on top of the current highlight.
+    fn remark(self: &mut Rc<Self>) {
+        let content = match &self.svg_content.borrow().svg_content {
+            Some(svg_content) => svg_content.clone(),
+            None => return,
+        };
+        let slot = self.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+        match rnb_core::mark(&content, slot.as_deref(), &self.selected, &self.current_floor()) {
+            Ok(marked) => Rc::make_mut(self).svg_content_highlighted.borrow_mut().svg_content = Some(marked),
+            Err(e) => clog!(e),
         }
-        let rr = nest.build();
-        
-        Ok(rr)
     }
 }
\ No newline at end of file
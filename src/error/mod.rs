@@ -0,0 +1,127 @@
+use std::{fmt, rc::Rc};
+
+use serde::{Serialize, Deserialize};
+use yew::{function_component, html, use_context, Callback, Html, MouseEvent, Properties, Reducible, UseReducerHandle};
+use yew_router::prelude::Link;
+
+use crate::{api::ApiError, Route};
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum AppError {
+    NotFound(String),
+    Server(String),
+    MalformedSvg(String),
+    Offline,
+    Unauthorized,
+    // the browser refused to keep something, e.g. in private mode or over quota
+    Storage(String),
+    Unexpected(String),
+}
+impl AppError {
+    // `code` names what was asked for, so a 404 can say which plan is missing.
+    pub fn from_api(e: ApiError, code: &str) -> Self {
+        match e {
+            ApiError::Network(_) => AppError::Offline,
+            ApiError::NotFound => AppError::NotFound(code.to_string()),
+            ApiError::Unauthorized => AppError::Unauthorized,
+            ApiError::Status(status, text) => AppError::Server(format!("{} {}", status, text)),
+            ApiError::Decode(e) => AppError::Unexpected(e),
+        }
+    }
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AppError::Server(_) | AppError::Offline | AppError::Unexpected(_))
+    }
+}
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound(code) => write!(f, "There is no plan with code \"{}\".", code),
+            AppError::Server(e) => write!(f, "The server could not handle the request ({}).", e),
+            AppError::MalformedSvg(e) => write!(f, "The plan drawing could not be read: {}.", e),
+            AppError::Offline => write!(f, "The server is unreachable. Check your connection."),
+            AppError::Unauthorized => write!(f, "Wrong username or password, or your session has expired."),
+            AppError::Storage(e) => write!(f, "The browser could not save your session: {}.", e),
+            AppError::Unexpected(e) => write!(f, "Unexpected response from the server: {}.", e),
+        }
+    }
+}
+
+//LINK - Toasts Reducible
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Toasts {
+    next_id: usize,
+    pub items: Vec<(usize, AppError)>,
+}
+pub enum ToastAction {
+    Push(AppError),
+    Dismiss(usize),
+}
+impl Reducible for Toasts {
+    type Action = ToastAction;
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut toasts = (*self).clone();
+        match action {
+            ToastAction::Push(error) => {
+                toasts.items.push((toasts.next_id, error));
+                toasts.next_id += 1;
+            },
+            ToastAction::Dismiss(id) => {
+                toasts.items.retain(|(item_id, _)| *item_id != id);
+            }
+        }
+        toasts.into()
+    }
+}
+pub type ToastContext = UseReducerHandle<Toasts>;
+
+#[function_component(ToastList)]
+pub fn toast_list() -> Html {
+    let toasts = use_context::<ToastContext>().expect("no Toast ctx found");
+    html! {
+        <div class="rnb-toasts" role="status">
+        { for toasts.items.iter().map(|(id, error)| {
+            let ondismiss = {
+                let toasts = toasts.clone();
+                let id = *id;
+                Callback::from(move |_: MouseEvent| toasts.dispatch(ToastAction::Dismiss(id)))
+            };
+            html! {
+                <div class="rnb-toast" key={*id}>
+                    <span>{error.to_string()}</span>
+                    <button onclick={ondismiss}>{"Dismiss"}</button>
+                </div>
+            }
+        }) }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ErrorViewProps {
+    pub error: AppError,
+    pub onretry: Callback<MouseEvent>,
+}
+
+//LINK - ErrorView
+#[function_component(ErrorView)]
+pub fn error_view(props: &ErrorViewProps) -> Html {
+    let title = match &props.error {
+        AppError::NotFound(_) => "Plan not found",
+        AppError::Server(_) => "Server error",
+        AppError::MalformedSvg(_) => "Broken drawing",
+        AppError::Offline => "Offline",
+        AppError::Unauthorized => "Not signed in",
+        AppError::Storage(_) => "Storage unavailable",
+        AppError::Unexpected(_) => "Something went wrong",
+    };
+    html! {
+        <div class="rnb-error" role="alert">
+            <h2>{title}</h2>
+            <p>{props.error.to_string()}</p>
+            if props.error.is_retryable() {
+                <button onclick={props.onretry.clone()}>{"Retry"}</button>
+            }
+            <Link<Route> to={Route::Home}>{"Back to home"}</Link<Route>>
+        </div>
+    }
+}
@@ -1,8 +1,14 @@
 use yew::prelude::*;
-use web_sys::{Event, EventTarget};
+use yew::suspense::{use_future_with, Suspension, SuspensionResult};
+use web_sys::{AbortController, Event, EventTarget};
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use gloo::events::EventListener;
+use rnb_core::{liquid::Svg, EntityResponse, SiteResponse};
+
+use crate::{api, error::AppError};
 
 
 #[hook]
@@ -42,4 +48,124 @@ where
             }
         },
     );
-}
\ No newline at end of file
+}
+
+thread_local! {
+    // Entities fetched this session, keyed by code. Only successful loads are kept.
+    static ENTITY_CACHE: RefCell<HashMap<String, EntityResponse>> = RefCell::new(HashMap::new());
+}
+
+pub struct UseEntityHandle {
+    pub result: Result<EntityResponse, AppError>,
+    pub retry: Callback<()>,
+}
+
+// Loads the entity for `code`, suspending until it arrives. Changing `code`
+// aborts the request in flight; codes seen before are served from memory.
+#[hook]
+pub fn use_entity(code: &str) -> SuspensionResult<UseEntityHandle> {
+    let output: UseStateHandle<Option<(String, u32, Result<EntityResponse, AppError>)>> = use_state(|| None);
+    let attempt = use_state(|| 0u32);
+    let latest_id = use_mut_ref(|| 0u32);
+    let controller = use_mut_ref(|| None::<AbortController>);
+
+    let suspension = {
+        let output = output.clone();
+        let controller = controller.clone();
+        use_memo((code.to_string(), *attempt), move |(code, attempt)| {
+            if let Some(previous) = controller.borrow_mut().take() {
+                previous.abort();
+            }
+            if ENTITY_CACHE.with(|cache| cache.borrow().contains_key(code)) {
+                return None;
+            }
+
+            let self_id = latest_id.borrow().wrapping_add(1);
+            *latest_id.borrow_mut() = self_id;
+            let abort = AbortController::new().ok();
+            let signal = abort.as_ref().map(|abort| abort.signal());
+            *controller.borrow_mut() = abort;
+
+            let code = code.clone();
+            let attempt = *attempt;
+            Some(Suspension::from_future(async move {
+                let result = api::ApiClient::from_document().fetch_entity(&code, signal.as_ref()).await;
+                if signal.map(|signal| signal.aborted()).unwrap_or(false) || *latest_id.borrow() != self_id {
+                    return;
+                }
+                let result = match result {
+                    Ok(entity) => match entity.svg_raw_content.as_deref().map(Svg::parse) {
+                        Some(Err(e)) => Err(AppError::MalformedSvg(e.to_string())),
+                        _ => {
+                            ENTITY_CACHE.with(|cache| cache.borrow_mut().insert(code.clone(), entity.clone()));
+                            Ok(entity)
+                        }
+                    },
+                    Err(e) => Err(AppError::from_api(e, &code)),
+                };
+                output.set(Some((code, attempt, result)));
+            }))
+        })
+    };
+
+    {
+        let controller = controller.clone();
+        use_effect_with((), move |_| {
+            move || {
+                if let Some(controller) = controller.borrow_mut().take() {
+                    controller.abort();
+                }
+            }
+        });
+    }
+
+    let retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_| attempt.set(*attempt + 1))
+    };
+
+    if let Some(entity) = ENTITY_CACHE.with(|cache| cache.borrow().get(code).cloned()) {
+        return Ok(UseEntityHandle { result: Ok(entity), retry });
+    }
+    match (&*output, &*suspension) {
+        (Some((loaded, loaded_attempt, result)), _) if loaded == code && *loaded_attempt == *attempt => {
+            Ok(UseEntityHandle { result: result.clone(), retry })
+        },
+        (_, Some(suspension)) if !suspension.resumed() => Err(suspension.clone()),
+        _ => Ok(UseEntityHandle { result: Err(AppError::Unexpected("the request was cancelled".to_string())), retry }),
+    }
+}
+
+pub struct UseSiteHandle {
+    pub result: Result<SiteResponse, AppError>,
+    pub retry: Callback<()>,
+}
+
+// Loads the site for `code`, suspending until it arrives. Its buildings go
+// into the entity cache, so opening one does not fetch it again.
+#[hook]
+pub fn use_site(code: &str) -> SuspensionResult<UseSiteHandle> {
+    let attempt = use_state(|| 0u32);
+    let site = use_future_with((code.to_string(), *attempt), |deps| async move {
+        let code = &deps.0;
+        let site = api::ApiClient::from_document()
+            .fetch_site(code, None)
+            .await
+            .map_err(|e| AppError::from_api(e, &format!("site/{}", code)))?;
+        ENTITY_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            for building in site.buildings.iter() {
+                // malformed drawings are left for `use_entity` to report
+                if !matches!(building.entity.svg_raw_content.as_deref().map(Svg::parse), Some(Err(_))) {
+                    cache.insert(building.placement.code.clone(), building.entity.clone());
+                }
+            }
+        });
+        Ok(site)
+    })?;
+    let retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_| attempt.set(*attempt + 1))
+    };
+    Ok(UseSiteHandle { result: (*site).clone(), retry })
+}
@@ -17,13 +17,28 @@ use gloo::{console::log as clog, events::EventListener, net::http::Request};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use regex::Regex;
+use rnb_core::liquid::Svg;
+
+mod api;
+
+mod hooks;
+
+mod plan;
+
+mod viewport;
+
+mod stack;
+
+mod site;
+
+mod shortcuts;
+
+mod error;
+use error::{AppError, ErrorView, ToastAction, ToastContext, ToastList, Toasts};
 
 mod entity;
 use entity::*;
 
-mod svg;
-use svg::*;
-
 #[derive(Properties, PartialEq, Clone)]
 struct SessionToken {
     value: String
@@ -37,6 +52,8 @@ pub enum Route {
     EditorLogin,
     #[at("/editor/dashboard")]
     EditorDashboard,
+    #[at("/site/:code")]
+    Site { code: String },
     
     #[at("/:code")]
     Code { code: String },
@@ -52,6 +69,9 @@ fn switch(routes: Route) -> Html {
         Route::EditorDashboard => html! {
             <EditorDashboard />
         },
+        Route::Site {code} => html! {
+            <site::Site code={code}/>
+        },
         Route::Code {code} => html! {
             <Code code={code}/>
         }
@@ -63,58 +83,49 @@ struct LoginForm {
     username: String,
     password: String,
 }
-async fn login(params_json: &Value) -> Option<User> {
-
-    let url_str = "http://127.0.0.2:8081/editor/login";
-    let req = reqwasm::http::Request::post(url_str)
-    .header("content-type", "applcation/json")
-    .body(params_json.to_string())
-    .send()
-    .await;
-    clog!(format!("req: {:?}", req));
-
-    match req {
-        Ok(response) => {
-            if response.status_text() == "OK" {
-                clog!("Success to submit form");
-                //let session_response = response.text().await.expect("Failed to read response body");
-                //let mut session_token_cl = session_token.deref().clone();
-                //session_token_cl.value = session_response;
-                //session_token.set(session_token_cl);
-                //
-                //has_user_state.set(HasUser{ user: Some(User {
-                //    id: 1,
-                //    username: "str".to_owned(),
-                //    token: "tok".to_owned()
-                //})});
-                let user = response.json::<User>().await.expect("err");
-                let local_storage = web_sys::window()
+async fn login(form: &LoginForm) -> Result<User, AppError> {
+    match api::ApiClient::from_document().login(form).await {
+        Ok(user) => {
+            clog!("Success to submit form");
+            // the session is kept for the next visit; without it the login is lost
+            let local_storage = web_sys::window()
                 .and_then(|win| win.local_storage().ok())
-                .and_then(|storage| storage)
-                .expect("LocalStorage is not available");
-                let user_json = serde_json::to_string(&user).expect("Failed to serialize user to JSON");
-                local_storage
-                    .set_item("user", &user_json)
-                    .expect("Failed to set item in localStorage");
-                return Some(user);
-            } else {
-                clog!("Non-ok status");
-                return None;
-            }
-        }
-        Err(err) => {
-            clog!("response err");
-            return None;
+                .flatten()
+                .ok_or(AppError::Storage("local storage is not available".to_string()))?;
+            let user_json = serde_json::to_string(&user).map_err(|e| AppError::Storage(e.to_string()))?;
+            local_storage
+                .set_item("user", &user_json)
+                .map_err(|_| AppError::Storage("local storage is full or disabled".to_string()))?;
+            Ok(user)
         }
+        Err(e) => Err(AppError::from_api(e, "editor/login")),
     }
-
 }
 //LINK - EditorDashboard
 #[function_component(EditorDashboard)]
 fn editor_dashboard() -> Html {
+    let user_state_context = use_context::<UserStateContext>().expect("no User ctx found");
+    let onlogout = {
+        let has_user_ctx = user_state_context.clone();
+        Callback::from(move |_: MouseEvent| {
+            let has_user_ctx = has_user_ctx.clone();
+            if let Some(user) = has_user_ctx.has_user.clone() {
+                spawn_local(async move {
+                    if let Err(e) = api::ApiClient::from_document().logout(&user).await {
+                        clog!(format!("logout failed: {}", e));
+                    }
+                    if let Some(local_storage) = window().and_then(|win| win.local_storage().ok()).flatten() {
+                        let _ = local_storage.remove_item("user");
+                    }
+                    has_user_ctx.dispatch(None);
+                });
+            }
+        })
+    };
     html! {
     <>
         <div>{"hello from dashboard"}</div>
+        <button onclick={onlogout}>{"Logout"}</button>
     </>
     }
 }
@@ -152,11 +163,23 @@ use option::*;
 #[function_component(Options)]
 fn options() -> Html {
     let entity_ctx = use_context::<EntityContext>().expect("no svg ctx");
+    let onlabelschange = {
+        let entity_ctx = entity_ctx.clone();
+        Callback::from(move |event: Event| {
+            let checked = event.target_unchecked_into::<HtmlInputElement>().checked();
+            entity_ctx.dispatch(EntityCase::Labels(checked));
+        })
+    };
     
     html! {
     <>
         <option::y::Y />
         <option::x::X />
+        <option::search::Search />
+        <label class="rnb-labels-toggle">
+            <input type="checkbox" checked={entity_ctx.show_labels} onchange={onlabelschange} />
+            {"Labels"}
+        </label>
     </>
     }
 }
@@ -166,174 +189,118 @@ mod theme;
 fn code(code: &CodeProp) -> Html {
     let user_ctx = use_context::<UserStateContext>().expect("no User ctx found");
     let entity_ctx = use_reducer(|| Entity::new() );
-    let focus = use_state(|| theme::Focus {
-        stroke: "#000000 !important".to_owned(),
-    });
-    let unfocus = use_state(|| theme::Unfocus {
-        stroke: "none !important".to_owned(),
-        fill: "none !important".to_owned()
-    });
+    let preset = use_state(theme::stored_preset);
+    {
+        let entity_ctx = entity_ctx.clone();
+        use_effect_with((*preset).clone(), move |preset| {
+            entity_ctx.dispatch(EntityCase::Theme(theme::Theme::preset(preset).unwrap_or_default()));
+        });
+    }
+    let onthemechange = {
+        let preset = preset.clone();
+        Callback::from(move |name: String| {
+            theme::store_preset(&name);
+            preset.set(name);
+        })
+    };
     let fallback = html! {<div>{"Loading..."}</div>};
     html! {
     <>    
-        <ContextProvider<theme::Focus> context={(*focus).clone()}>
-        <ContextProvider<theme::Unfocus> context={(*unfocus).clone()}>
+        <style>{entity_ctx.theme().stylesheet()}</style>
+        <ContextProvider<theme::Theme> context={entity_ctx.theme()}>
         <ContextProvider<EntityContext> context={entity_ctx}>
             <Options/> 
-            <SvgData code={code.code.clone()}/>
+            <theme::ThemePicker value={(*preset).clone()} onchange={onthemechange} />
+            <shortcuts::Shortcuts />
+            <Suspense fallback={fallback}>
+                <SvgData code={code.code.clone()}/>
+            </Suspense>
         </ContextProvider<EntityContext>>
-        </ContextProvider<theme::Unfocus>>
-        </ContextProvider<theme::Focus>>
+        </ContextProvider<theme::Theme>>
     </>
     }
 }
 
 #[function_component(SvgData)]
-fn svg_data(code: &CodeProp) -> Html {
+fn svg_data(code: &CodeProp) -> HtmlResult {
     let code: String = code.code.clone();
     let entity_ctx: UseReducerHandle<Entity> = use_context::<EntityContext>().expect("no Svg Content ctx found");
-    if entity_ctx.name.borrow().is_empty() {
-        spawn_local(async move {
-            let svg_req: Result<reqwasm::http::Response, reqwasm::Error> = reqwasm::http::Request::get(
-                &format!("http://127.0.0.2:8081/{:?}", code)
-            )
-            .send()
-            .await;
-            match svg_req {
-                Ok(response) => {
-                    if response.status_text() == "OK" {
-                        let body_text = response.text().await.expect("Failed to get body text");
-                        let entity: Result<EntityResponse, serde_json::Error>  = serde_json::from_str(&body_text);
-                        
-                        if let Ok(entity) = entity {
-                            let ent = entity::Entity::to_entity(entity);
-                            entity_ctx.dispatch(EntityCase::Init(Some(ent)));
-                            entity_ctx.dispatch(EntityCase::ProduceOption(None));
-                            entity_ctx.dispatch(EntityCase::Highlight("".to_string()));
-                        }
-                    }
-                },
-                Err(_) => {
-                }
+    let entity = hooks::use_entity(&code)?;
+    {
+        let entity_ctx = entity_ctx.clone();
+        let loaded = entity.result.clone().ok();
+        use_effect_with((code.clone(), loaded.is_some()), move |_| {
+            if let Some(loaded) = loaded {
+                let ent = entity::Entity::to_entity(loaded);
+                entity_ctx.dispatch(EntityCase::Init(Some(ent)));
+                entity_ctx.dispatch(EntityCase::ProduceOption(None));
+                entity_ctx.dispatch(EntityCase::ClearHighlight);
             }
         });
-    } 
-    
-    let context = use_context::<EntityContext>().expect("no Svg Content ctx found");
-    let svg_content = context.svg_content.borrow();
-    let svg_content_highlighted = context.svg_content_highlighted.borrow();
-
-    let div: Element = document().create_element("div").unwrap();
-    let target: HtmlElement = div.clone().dyn_into::<HtmlElement>().unwrap();
-    let is_dragging: Rc<Cell<bool>> = Rc::new(Cell::new(false));
-    let offset_x: Rc<Cell<i32>> = Rc::new(Cell::new(0));
-    let offset_y: Rc<Cell<i32>> = Rc::new(Cell::new(0));
-
-    let saved_left: Rc<Cell<i32>> = Rc::new(Cell::new(0)); // Initial left position
-    let saved_top: Rc<Cell<i32>> = Rc::new(Cell::new(0));  // Initial top position
-
-    {
-        let is_dragging_clone: Rc<Cell<bool>> = is_dragging.clone();
-        let offset_x_clone: Rc<Cell<i32>> = offset_x.clone();
-        let offset_y_clone: Rc<Cell<i32>> = offset_y.clone();
-        let saved_left_clone: Rc<Cell<i32>> = saved_left.clone();
-        let saved_top_clone: Rc<Cell<i32>> = saved_top.clone();
-
-        let on_mousedown = Closure::wrap(Box::new(move |event: MouseEvent| {
-            is_dragging_clone.set(true);
-    
-            offset_x_clone.set(event.client_x() - saved_left_clone.get());
-            offset_y_clone.set(event.client_y() - saved_top_clone.get());
-        }) as Box<dyn FnMut(_)>);
-
-            div.add_event_listener_with_callback("mousedown", on_mousedown.as_ref().unchecked_ref())
-            .unwrap();
-        on_mousedown.forget();
     }
-
-    {
-        let target = target.clone();
-        let is_dragging_clone = is_dragging.clone();
-        let offset_x_clone = offset_x.clone();
-        let offset_y_clone = offset_y.clone();
-
-        let on_mousemove = Closure::wrap(Box::new(move |event: MouseEvent| {
-            if is_dragging_clone.get() {
-                let new_left = event.client_x() - offset_x_clone.get();
-                let new_top = event.client_y() - offset_y_clone.get();
-                target
-                .set_attribute("style", 
-                    &format!(
-                        "
-                            position: relative !important; 
-                            overflow: hidden;
-                            top: {}px;
-                            left: {}px;
-                            right: {}px;
-                        ", 
-                        new_top,
-                        new_left,
-                        -new_left 
-                    )
-                )
-                .unwrap();
+    
+    // dispatching only needs the reducer, so one callback serves every render
+    let onselect = {
+        let entity_ctx = entity_ctx.clone();
+        use_callback((), move |indices: Vec<usize>, _| entity_ctx.dispatch(EntityCase::Select(indices)))
+    };
+    let onselectextend = {
+        let entity_ctx = entity_ctx.clone();
+        use_callback((), move |indices: Vec<usize>, _| entity_ctx.dispatch(EntityCase::SelectMore(indices)))
+    };
+    let stacked = use_state(|| false);
+    let onstackedtoggle = {
+        let stacked = stacked.clone();
+        Callback::from(move |_: MouseEvent| stacked.set(!*stacked))
+    };
+    // a floor picked in the stacked view opens in 2D
+    let onfloor = {
+        let entity_ctx = entity_ctx.clone();
+        let stacked = stacked.clone();
+        Callback::from(move |floor: String| {
+            if floor != entity_ctx.current_floor() {
+                entity_ctx.dispatch(EntityCase::ProduceOption(Some(floor)));
             }
-        }) as Box<dyn FnMut(_)>);
-
-        div
-            .add_event_listener_with_callback("mousemove", on_mousemove.as_ref().unchecked_ref())
-            .unwrap();
-        on_mousemove.forget();
-    }
-
-    {
-        let target = target.clone();
-        let is_dragging_clone = is_dragging.clone();
-        let saved_left_clone = saved_left.clone();
-        let saved_top_clone = saved_top.clone();
-
-        let on_mouseup = Closure::wrap(Box::new(move |_event: MouseEvent| {
-            is_dragging_clone.set(false);
-            let current_left = target.get_attribute("style")
-                .and_then(|style| {
-                    style.split(';')
-                        .find(|s| s.trim_start().starts_with("left:"))
-                        .map(|s| s.replace("left:", "").replace("px", "").trim().parse::<i32>().unwrap())
-                }).unwrap_or(0); 
-            let current_top = target.get_attribute("style")
-                .and_then(|style| {
-                    style.split(';')
-                        .find(|s| s.trim_start().starts_with("top:"))
-                        .map(|s| s.replace("top:", "").replace("px", "").trim().parse::<i32>().unwrap())
-                }).unwrap_or(0); 
-            
-            saved_left_clone.set(current_left);
-            saved_top_clone.set(current_top);
-        }) as Box<dyn FnMut(_)>);
-
-        div
-            .add_event_listener_with_callback("mouseup", on_mouseup.as_ref().unchecked_ref())
-            .unwrap();
-        on_mouseup.forget();
-    }
-
-    let svge = if let Some(svge) = &svg_content_highlighted.svg_content {
-        svge
-    } else if let Some(svge) = &svg_content.svg_content {
-        svge
-    } else {
-        "upload svg"
+            stacked.set(false);
+        })
+    };
+    // the same floors and slots as plain lists, for screen readers
+    let listed = use_state(|| false);
+    let onlistedtoggle = {
+        let listed = listed.clone();
+        Callback::from(move |_: MouseEvent| listed.set(!*listed))
     };
 
-    div.set_inner_html(svge);
+    let context = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    if let Err(error) = entity.result.clone() {
+        let onretry = entity.retry.reform(|_: MouseEvent| ());
+        return Ok(html! {
+            <ErrorView error={error} onretry={onretry} />
+        });
+    }
 
-    let node: Node = div.into();
-    
-    return html! {
+    Ok(html! {
     <>
-        {Html::VRef(node)}
+        if !*listed {
+            <button class="rnb-stack-toggle" onclick={onstackedtoggle}>
+                { if *stacked { "Floor view" } else { "Stacked view" } }
+            </button>
+        }
+        <button class="rnb-list-toggle" aria-pressed={(*listed).to_string()} onclick={onlistedtoggle}>
+            {"List view"}
+        </button>
+        if *listed {
+            <plan::list::PlanList />
+        } else if *stacked {
+            <stack::StackedView {onfloor} />
+        } else {
+            <viewport::Viewport {onselect} {onselectextend} />
+        }
+        <plan::details::SlotDetails />
+        <plan::compare::SelectionList />
+        <plan::compare::ComparePanel />
     </>
-    }
+    })
 }
 //LINK - EditorLogin
 #[function_component(EditorLogin)]
@@ -345,9 +312,11 @@ fn editor_login() -> Html {
     });
 
     let user_state_context = use_context::<UserStateContext>().unwrap();
+    let toast_ctx = use_context::<ToastContext>().expect("no Toast ctx found");
 
     let onsubmit = {
         let has_user_ctx = user_state_context.clone();
+        let toast_ctx = toast_ctx.clone();
         let username = username.clone().to_string();
         let password = password.clone().to_string();
         let session_token = session_token.clone();
@@ -355,17 +324,23 @@ fn editor_login() -> Html {
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
             let has_user_ctx = has_user_ctx.clone();
+            let toast_ctx = toast_ctx.clone();
             let username = username.clone().to_string();
             let password = password.clone().to_string();
             let session_token = session_token.clone();
-            let params_json = json!({
-                "username": username.to_string(),
-                "password": password.to_string()
-            });
+            let form = LoginForm {
+                username: username.to_string(),
+                password: password.to_string()
+            };
             
             spawn_local(async move {
-                let usere = login(&params_json).await;
-                has_user_ctx.dispatch(usere);
+                match login(&form).await {
+                    Ok(user) => has_user_ctx.dispatch(Some(user)),
+                    Err(e) => {
+                        has_user_ctx.dispatch(None);
+                        toast_ctx.dispatch(ToastAction::Push(e));
+                    }
+                }
             });
         })
     };
@@ -414,45 +389,43 @@ fn editor_login() -> Html {
     }
 }
 
-fn app_load_state(local_storage_user: Option<String>, local_storage: Storage, has_user_ctx: UseReducerHandle<UserState>) -> Result<(), Error> {
+fn app_load_state(local_storage_user: Option<String>, local_storage: Option<Storage>, has_user_ctx: UseReducerHandle<UserState>) -> Result<(), Error> {
     if let Some(user_data) = local_storage_user {
         let match_user_data: Result<Option<User>, serde_json::Error> = serde_json::from_str(&user_data);
         let user_state_contexta = has_user_ctx.clone();
         match match_user_data {
-            Ok(data) => {
+            Ok(Some(user)) => {
                 let local_storage = local_storage.clone();
                 spawn_local(async move {
                     let user_state_contextb = user_state_contexta.clone();
-                    let url_str = "http://127.0.0.2:8081/editor/reauth";
-                    let req = reqwasm::http::Request::post(url_str)
-                    .header("content-type", "applcation/json")
-                    .body(user_data)
-                    .send()
-                    .await;
-                    match req {
-                        Ok(response) => {
-                            if response.status_text() == "OK" {
-                                user_state_contextb.dispatch(data);
-                            } else {
-                                let _remove_loaded = local_storage.remove_item("user");
-                                user_state_contextb.dispatch(None);
-                            }
+                    match api::ApiClient::from_document().reauth(&user).await {
+                        Ok(()) => {
+                            user_state_contextb.dispatch(Some(user));
+                        },
+                        Err(api::ApiError::Network(_)) => {
+                            user_state_contextb.dispatch(None);
                         },
                         Err(_) => {
+                            if let Some(local_storage) = &local_storage {
+                                let _remove_loaded = local_storage.remove_item("user");
+                            }
                             user_state_contextb.dispatch(None);
                         }
                     };
                 });
             },
+            Ok(None) => {
+                user_state_contexta.dispatch(None);
+            },
             Err(_) => {
                 user_state_contexta.dispatch(None);
             },
         };
     };
     let app_state = AppState { loaded: true };
-    local_storage
-    .set_item("loaded", &serde_json::to_string(&app_state).unwrap())
-    .expect("Failed to set item in localStorage");
+    if let (Some(local_storage), Ok(app_state)) = (local_storage, serde_json::to_string(&app_state)) {
+        let _ = local_storage.set_item("loaded", &app_state);
+    }
     Ok(())
 }
 
@@ -587,18 +560,18 @@ type EntityContext = UseReducerHandle<Entity>;
 
 #[function_component(App)]
 pub fn app() -> Html {
+    // without storage, e.g. in private mode, there is simply no stored user
     let local_storage = web_sys::window()
     .and_then(|win| win.local_storage().ok())
-    .and_then(|storage| storage)
-    .expect("LocalStorage is not available");
-    let local_storage_user = local_storage
-        .get_item("user")
-        .expect("Failed to get item from localStorage");
-    let local_storage_loaded = local_storage
-        .get_item("loaded")
-        .expect("Failed to get item from localStorage");
-    let _remove_loaded = local_storage.remove_item("loaded");
+    .flatten();
+    let stored = |key: &str| local_storage.as_ref().and_then(|storage| storage.get_item(key).ok().flatten());
+    let local_storage_user = stored("user");
+    let local_storage_loaded = stored("loaded");
+    if let Some(local_storage) = &local_storage {
+        let _remove_loaded = local_storage.remove_item("loaded");
+    }
     let user_state = use_reducer(|| UserState {has_user: None});
+    let toasts = use_reducer(Toasts::default);
     
     let onload = {
         let local_storage = local_storage.clone();
@@ -635,6 +608,7 @@ pub fn app() -> Html {
     html! {
     <>
         <ContextProvider<UserStateContext> context={user_state}>
+        <ContextProvider<ToastContext> context={toasts}>
         <div {onload}>
             <BrowserRouter>
             <nav>
@@ -646,8 +620,10 @@ pub fn app() -> Html {
             <main>
                 <Switch<Route> render={switch} />
             </main>
+            <ToastList />
             </BrowserRouter>
         </div>
+        </ContextProvider<ToastContext>>
         </ContextProvider<UserStateContext>>
     </>
     }
@@ -8,5 +8,6 @@ use web_sys::{js_sys::{self, Error, JSON}, window, Element, EventTarget, HtmlInp
 
 use crate::{EntityContext, _Entity::default_floor, entity, Entity, EntityCase};
 
+pub mod search;
 pub mod y;
 pub mod x;
@@ -0,0 +1,129 @@
+use rnb_core::search::rank;
+use web_sys::HtmlInputElement;
+use yew::{function_component, html, use_context, use_state, Callback, Html, InputEvent, KeyboardEvent, MouseEvent, TargetCast};
+
+use crate::{EntityCase, EntityContext};
+
+const MAX_RESULTS: usize = 8;
+
+struct SlotEntry {
+    slot: String,
+    floor: String,
+    name: String,
+    text: String,
+}
+
+//LINK - Search
+#[function_component(Search)]
+pub fn search() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let query = use_state(String::new);
+    let active = use_state(|| 0usize);
+
+    let entries: Vec<SlotEntry> = if query.trim().is_empty() {
+        Vec::new()
+    } else {
+        ctx.slots()
+            .into_iter()
+            .filter_map(|(slot, floor)| {
+                let element_tags = ctx.slot_tags(&slot, &floor);
+                if element_tags.iter().any(|element_tag| element_tag.is_disabled()) {
+                    return None;
+                }
+                let name = element_tags
+                    .iter()
+                    .find_map(|element_tag| element_tag.slot.clone())
+                    .unwrap_or(slot.clone());
+                // everything the id says about the slot is searchable
+                let text = element_tags
+                    .iter()
+                    .flat_map(|element_tag| element_tag.tokens())
+                    .chain([name.clone(), ctx.floor(&floor).label()])
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                Some(SlotEntry { slot, floor, name, text })
+            })
+            .collect()
+    };
+    let results: Vec<(String, String, String)> = rank(&entries, &query, |entry| entry.text.clone())
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|entry| (entry.slot.clone(), entry.floor.clone(), entry.name.clone()))
+        .collect();
+
+    let choose = {
+        let ctx = ctx.clone();
+        let query = query.clone();
+        let active = active.clone();
+        Callback::from(move |(slot, floor): (String, String)| {
+            if floor != ctx.current_floor() {
+                ctx.dispatch(EntityCase::ProduceOption(Some(floor)));
+            }
+            ctx.dispatch(EntityCase::Highlight(slot));
+            query.set(String::new());
+            active.set(0);
+        })
+    };
+    let oninput = {
+        let query = query.clone();
+        let active = active.clone();
+        Callback::from(move |event: InputEvent| {
+            query.set(event.target_unchecked_into::<HtmlInputElement>().value());
+            active.set(0);
+        })
+    };
+    let onkeydown = {
+        let query = query.clone();
+        let active = active.clone();
+        let choose = choose.clone();
+        let results = results.clone();
+        Callback::from(move |event: KeyboardEvent| match event.key().as_str() {
+            "ArrowDown" if !results.is_empty() => {
+                event.prevent_default();
+                active.set((*active + 1) % results.len());
+            }
+            "ArrowUp" if !results.is_empty() => {
+                event.prevent_default();
+                active.set((*active + results.len() - 1) % results.len());
+            }
+            "Enter" => {
+                if let Some((slot, floor, _)) = results.get(*active) {
+                    choose.emit((slot.clone(), floor.clone()));
+                }
+            }
+            "Escape" => query.set(String::new()),
+            _ => {}
+        })
+    };
+
+    html! {
+        <div class="rnb-search">
+            <input
+                type="search"
+                placeholder="Find a slot"
+                value={(*query).clone()}
+                {oninput}
+                {onkeydown}
+            />
+            if !results.is_empty() {
+                <ul class="rnb-search-results">
+                { for results.into_iter().enumerate().map(|(index, (slot, floor, name))| {
+                    let onclick = {
+                        let choose = choose.clone();
+                        let chosen = (slot.clone(), floor.clone());
+                        Callback::from(move |_: MouseEvent| choose.emit(chosen.clone()))
+                    };
+                    html! {
+                        <li key={format!("{}_{}", slot, floor)} class={(index == *active).then_some("active")} {onclick}>
+                            {name}
+                            <small>{format!(" {}", ctx.floor(&floor).label())}</small>
+                        </li>
+                    }
+                }) }
+                </ul>
+            } else if !query.trim().is_empty() {
+                <div class="rnb-search-empty">{"No matching slots"}</div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,116 @@
+use rnb_core::{liquid::Svg, ElementTag, Measure};
+use yew::{function_component, html, use_context, use_memo, Callback, Event, Html, MouseEvent};
+
+use crate::{plan::details::{measure_text, slot_measure}, theme::Theme, EntityCase, EntityContext};
+
+//LINK - SelectionList
+#[function_component(SelectionList)]
+pub fn selection_list() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let floor = ctx.current_floor();
+    let slots = ctx.floor_slots();
+
+    html! {
+        <fieldset class="rnb-selection-list">
+            <legend>{"Compare"}</legend>
+            { for slots.into_iter().map(|slot| {
+                let checked = ctx.selected.contains(&(slot.clone(), floor.clone()));
+                let onchange = {
+                    let ctx = ctx.clone();
+                    let slot = slot.clone();
+                    Callback::from(move |_: Event| ctx.dispatch(EntityCase::ToggleSelect(slot.clone())))
+                };
+                html! {
+                    <label key={slot.clone()}>
+                        <input type="checkbox" {checked} {onchange} />
+                        {slot}
+                    </label>
+                }
+            }) }
+        </fieldset>
+    }
+}
+
+//LINK - ComparePanel
+#[function_component(ComparePanel)]
+pub fn compare_panel() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let theme = use_context::<Theme>().unwrap_or_default();
+    let content = ctx.svg_content.borrow().svg_content.clone();
+    let selected = ctx.selected.clone();
+    let measures = use_memo((content, selected.clone()), |(content, selected)| {
+        let svg = content.as_deref().and_then(|content| Svg::parse(content).ok());
+        selected
+            .iter()
+            .map(|(slot, floor)| svg.as_ref().map(|svg| slot_measure(svg, slot, floor)).unwrap_or_default())
+            .collect::<Vec<Measure>>()
+    });
+    if selected.is_empty() {
+        return html! {};
+    }
+
+    let columns: Vec<(String, Vec<ElementTag>, (String, String), String)> = selected
+        .iter()
+        .enumerate()
+        .map(|(position, (slot, floor))| {
+            let colour = theme
+                .palette
+                .get(position)
+                .and_then(|style| style.stroke.clone())
+                .unwrap_or_default();
+            (slot.clone(), ctx.slot_tags(slot, floor), measure_text(&measures[position], ctx.units_per_metre), colour)
+        })
+        .collect();
+    let row = |label: &str, value: &dyn Fn(&[ElementTag]) -> String| html! {
+        <tr>
+            <th scope="row">{label.to_string()}</th>
+            { for columns.iter().map(|(_, element_tags, _, _)| html! { <td>{value(element_tags)}</td> }) }
+        </tr>
+    };
+    let field = |get: fn(&ElementTag) -> Option<String>| {
+        move |element_tags: &[ElementTag]| element_tags.iter().find_map(get).unwrap_or_default()
+    };
+    let onclear = {
+        let ctx = ctx.clone();
+        Callback::from(move |_: MouseEvent| ctx.dispatch(EntityCase::ClearSelection))
+    };
+
+    html! {
+        <aside class="rnb-compare">
+            <table>
+                <thead>
+                    <tr>
+                        <th></th>
+                        { for columns.iter().map(|(slot, _, _, colour)| html! {
+                            <th scope="col" style={format!("border-bottom: 4px solid {};", colour)}>{slot.clone()}</th>
+                        }) }
+                    </tr>
+                </thead>
+                <tbody>
+                    <tr>
+                        <th scope="row">{"Floor"}</th>
+                        { for selected.iter().map(|(_, floor)| html! { <td>{ctx.floor(floor).label()}</td> }) }
+                    </tr>
+                    {row("Building", &field(|element_tag| element_tag.building.clone()))}
+                    {row("Zone", &field(|element_tag| element_tag.zone.clone()))}
+                    {row("Kind", &field(|element_tag| element_tag.kind.clone()))}
+                    {row("Tags", &|element_tags: &[ElementTag]| {
+                        let mut tags: Vec<String> = element_tags.iter().flat_map(|element_tag| element_tag.tags.clone()).collect();
+                        tags.sort();
+                        tags.dedup();
+                        tags.join(", ")
+                    })}
+                    <tr>
+                        <th scope="row">{"Area"}</th>
+                        { for columns.iter().map(|(_, _, (area, _), _)| html! { <td>{area.clone()}</td> }) }
+                    </tr>
+                    <tr>
+                        <th scope="row">{"Perimeter"}</th>
+                        { for columns.iter().map(|(_, _, (_, perimeter), _)| html! { <td>{perimeter.clone()}</td> }) }
+                    </tr>
+                </tbody>
+            </table>
+            <button onclick={onclear}>{"Clear selection"}</button>
+        </aside>
+    }
+}
@@ -0,0 +1,91 @@
+use rnb_core::{geometry::measure, liquid::Svg, Measure};
+use yew::{function_component, html, use_context, use_memo, Html};
+
+use crate::EntityContext;
+
+// `slot` on `floor` in drawing units.
+pub fn slot_measure(svg: &Svg, slot: &str, floor: &str) -> Measure {
+    measure(svg, |element_tag| {
+        element_tag.slot_token().as_deref() == Some(slot) && element_tag.floor_token().as_deref() == Some(floor)
+    })
+}
+
+// Area and perimeter as text, in metres when the entity gives a scale.
+pub fn measure_text(measure: &Measure, units_per_metre: Option<f64>) -> (String, String) {
+    match units_per_metre.filter(|units_per_metre| *units_per_metre > 0.0) {
+        Some(units_per_metre) => {
+            let scaled = measure.scaled(units_per_metre);
+            (format!("{:.1} m²", scaled.area), format!("{:.1} m", scaled.perimeter))
+        }
+        None => (format!("{:.0} units²", measure.area), format!("{:.0} units", measure.perimeter)),
+    }
+}
+
+//LINK - SlotDetails
+#[function_component(SlotDetails)]
+pub fn slot_details() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let floor = ctx.current_floor();
+    let slot = ctx.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+    let content = ctx.svg_content.borrow().svg_content.clone();
+    let measure = use_memo((content, slot.clone(), floor.clone()), |(content, slot, floor)| {
+        let svg = content.as_deref().and_then(|content| Svg::parse(content).ok())?;
+        Some(slot_measure(&svg, slot.as_deref()?, floor))
+    });
+    let slot = match slot {
+        Some(slot) => slot,
+        None => return html! {},
+    };
+    let element_tags = ctx.slot_tags(&slot, &floor);
+    let name = element_tags
+        .iter()
+        .find_map(|element_tag| element_tag.slot.clone())
+        .unwrap_or(slot.clone());
+
+    let mut tags: Vec<String> = element_tags.iter().flat_map(|element_tag| element_tag.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+    let field = |get: fn(&rnb_core::ElementTag) -> Option<String>| {
+        element_tags.iter().find_map(get)
+    };
+    let building = field(|element_tag| element_tag.building.clone());
+    let zone = field(|element_tag| element_tag.zone.clone());
+    let kind = field(|element_tag| element_tag.kind.clone());
+    let measure = (*measure)
+        .as_ref()
+        .filter(|measure| measure.area > 0.0 || measure.perimeter > 0.0)
+        .map(|measure| measure_text(measure, ctx.units_per_metre));
+
+    html! {
+        // focused by the Enter shortcut
+        <aside class="rnb-details" tabindex="-1">
+            <h3>{name}</h3>
+            <dl>
+                <dt>{"Floor"}</dt>
+                <dd>{ctx.floor(&floor).label()}</dd>
+                if let Some(building) = building {
+                    <dt>{"Building"}</dt>
+                    <dd>{building}</dd>
+                }
+                if let Some(zone) = zone {
+                    <dt>{"Zone"}</dt>
+                    <dd>{zone}</dd>
+                }
+                if let Some(kind) = kind {
+                    <dt>{"Kind"}</dt>
+                    <dd>{kind}</dd>
+                }
+                if !tags.is_empty() {
+                    <dt>{"Tags"}</dt>
+                    <dd>{tags.join(", ")}</dd>
+                }
+                if let Some((area, perimeter)) = measure {
+                    <dt>{"Area"}</dt>
+                    <dd>{area}</dd>
+                    <dt>{"Perimeter"}</dt>
+                    <dd>{perimeter}</dd>
+                }
+            </dl>
+        </aside>
+    }
+}
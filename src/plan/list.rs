@@ -0,0 +1,89 @@
+use rnb_core::a11y::{plan_name, slot_name};
+use yew::{function_component, html, use_context, Callback, Html, MouseEvent};
+
+use crate::{EntityCase, EntityContext};
+
+//LINK - PlanList
+// The plan as nested lists of floors, top first, and their slots, for screen
+// readers and keyboards. Picking an entry moves `current_option` just like the
+// drawing does, and the entries follow it back.
+#[function_component(PlanList)]
+pub fn plan_list() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let current_floor = ctx.current_floor();
+    let current_slot = ctx.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+
+    let choose = {
+        let ctx = ctx.clone();
+        Callback::from(move |(floor, slot): (String, Option<String>)| {
+            if floor != ctx.current_floor() {
+                ctx.dispatch(EntityCase::ProduceOption(Some(floor)));
+            }
+            if let Some(slot) = slot {
+                ctx.dispatch(EntityCase::Highlight(slot));
+            }
+        })
+    };
+    // (slot token, visible name, accessible name, disabled) of every slot on a floor
+    let floor_slots = |token: &str| -> Vec<(String, String, String, bool)> {
+        let floor = ctx.floor(token);
+        ctx.slots()
+            .into_iter()
+            .filter(|(_, slot_floor)| slot_floor == token)
+            .map(|(slot, _)| {
+                let element_tag = ctx.slot_tags(&slot, token).into_iter().next();
+                let name = element_tag.as_ref().and_then(|element_tag| element_tag.slot.clone()).unwrap_or(slot.clone());
+                let accessible = match &element_tag {
+                    Some(element_tag) => slot_name(element_tag, &floor),
+                    None => slot.clone(),
+                };
+                let disabled = element_tag.as_ref().is_some_and(|element_tag| element_tag.is_disabled());
+                (slot, name, accessible, disabled)
+            })
+            .collect()
+    };
+
+    let current = floor_slots(&current_floor);
+    let status = match current.iter().find(|(slot, _, _, _)| Some(slot) == current_slot.as_ref()) {
+        Some((_, _, accessible, _)) => accessible.clone(),
+        None => plan_name(&ctx.floor(&current_floor), current.len()),
+    };
+
+    html! {
+        <nav class="rnb-plan-list" aria-label="Floors and slots">
+            <p class="rnb-plan-list-status" role="status" aria-live="polite">{status}</p>
+            <ul>
+            { for ctx.floors().into_iter().rev().map(|floor| {
+                let is_current = floor.token == current_floor;
+                let onclick = {
+                    let choose = choose.clone();
+                    let token = floor.token.clone();
+                    Callback::from(move |_: MouseEvent| choose.emit((token.clone(), None)))
+                };
+                html! {
+                    <li key={floor.token.clone()}>
+                        <button aria-current={is_current.then_some("true")} {onclick}>{floor.label()}</button>
+                        <ul aria-label={format!("Slots on {}", floor.label())}>
+                        { for floor_slots(&floor.token).into_iter().map(|(slot, name, accessible, disabled)| {
+                            let is_highlighted = is_current && current_slot.as_ref() == Some(&slot);
+                            let onclick = {
+                                let choose = choose.clone();
+                                let chosen = (floor.token.clone(), Some(slot.clone()));
+                                Callback::from(move |_: MouseEvent| choose.emit(chosen.clone()))
+                            };
+                            html! {
+                                <li key={slot.clone()}>
+                                    <button aria-label={accessible} aria-current={is_highlighted.then_some("true")} {disabled} {onclick}>
+                                        {name}
+                                    </button>
+                                </li>
+                            }
+                        }) }
+                        </ul>
+                    </li>
+                }
+            }) }
+            </ul>
+        </nav>
+    }
+}
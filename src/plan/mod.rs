@@ -0,0 +1,382 @@
+use std::rc::Rc;
+
+use rnb_core::{
+    a11y::{plan_name, slot_name},
+    element_index,
+    liquid::{unescape, Element, Node, Svg, Tag},
+    slot_tag_at, ElementTag, Floor, ShapeState, ViewBox,
+};
+use web_sys::HtmlElement;
+use yew::{
+    function_component, html, use_callback, use_context, use_memo, use_mut_ref, use_node_ref, use_state,
+    Callback, Html, KeyboardEvent, MouseEvent, Properties,
+    virtual_dom::{ApplyAttributeAs, AttrValue, Attributes, VNode},
+};
+
+use crate::EntityContext;
+
+pub mod compare;
+pub mod details;
+pub mod list;
+
+// Path of child indices from `Svg::nodes`, as produced by `Svg::walk`.
+pub type NodePath = Vec<usize>;
+
+#[derive(Properties)]
+pub struct PlanNodeProps {
+    pub svg: Rc<Svg>,
+    pub path: NodePath,
+    #[prop_or_default]
+    pub onshapeclick: Option<Callback<NodePath>>,
+    #[prop_or_default]
+    pub onshapeenter: Option<Callback<NodePath>>,
+    #[prop_or_default]
+    pub onshapeleave: Option<Callback<NodePath>>,
+    // the hovered shape, only passed down the branch that leads to it
+    #[prop_or_default]
+    pub hovered: Option<NodePath>,
+    // replaces the root `<svg>` viewBox and stretches it over its container
+    #[prop_or_default]
+    pub view_box: Option<ViewBox>,
+    // slots on this floor become buttons a keyboard can reach
+    #[prop_or_default]
+    pub floor: Option<Rc<Floor>>,
+    // an ancestor already is the button of its slot
+    #[prop_or_default]
+    pub in_slot: bool,
+    // Enter or Space on a slot, with whether shift was held
+    #[prop_or_default]
+    pub onslotpress: Option<Callback<(NodePath, bool)>>,
+}
+// A node only re-renders when its own subtree changed, so highlighting a slot
+// patches the shapes of that slot and leaves the rest of the plan alone.
+impl PartialEq for PlanNodeProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.onshapeclick == other.onshapeclick
+            && self.onshapeenter == other.onshapeenter
+            && self.onshapeleave == other.onshapeleave
+            && self.hovered == other.hovered
+            && self.view_box == other.view_box
+            && self.floor == other.floor
+            && self.in_slot == other.in_slot
+            && self.onslotpress == other.onslotpress
+            && (Rc::ptr_eq(&self.svg, &other.svg) || self.svg.node_at(&self.path) == other.svg.node_at(&other.path))
+    }
+}
+
+fn shape_callback(callback: &Option<Callback<NodePath>>, path: &NodePath) -> Option<Callback<MouseEvent>> {
+    callback.as_ref().map(|callback| {
+        let path = path.clone();
+        callback.reform(move |_: MouseEvent| path.clone())
+    })
+}
+
+// Highlighting marks the slot's shapes, which may sit below the element that
+// names the slot.
+fn is_highlighted(element: &Element) -> bool {
+    element.property(ShapeState::ATTRIBUTE) == Some(ShapeState::Highlight.as_str())
+        || element.elements().any(is_highlighted)
+}
+
+//LINK - PlanNode
+#[function_component(PlanNode)]
+pub fn plan_node(props: &PlanNodeProps) -> Html {
+    let element = match props.svg.node_at(&props.path) {
+        Some(Node::Element(element)) => element,
+        Some(Node::Text(text)) => return html! { unescape(text) },
+        Some(Node::Raw(raw)) if raw.starts_with("<![CDATA[") => {
+            return html! { raw.trim_start_matches("<![CDATA[").trim_end_matches("]]>").to_string() };
+        }
+        _ => return html! {},
+    };
+
+    // only the outermost element naming a slot on the shown floor
+    let slot = match (&props.floor, props.in_slot) {
+        (Some(floor), false) if element.tag == Tag::G || element.tag.is_shape() => element
+            .id()
+            .and_then(|id| ElementTag::parse(id).ok())
+            .filter(|element_tag| element_tag.is_slot() && element_tag.floor_token().as_ref() == Some(&floor.token))
+            .map(|element_tag| (slot_name(&element_tag, floor), element_tag.is_disabled())),
+        _ => None,
+    };
+    let in_slot = props.in_slot || slot.is_some();
+
+    let children: Html = element
+        .children
+        .iter()
+        .enumerate()
+        .map(|(index, _)| {
+            let mut path = props.path.clone();
+            path.push(index);
+            let hovered = props.hovered.clone().filter(|hovered| hovered.starts_with(&path));
+            html! {
+                <PlanNode
+                    svg={props.svg.clone()}
+                    {path}
+                    {hovered}
+                    floor={props.floor.clone()}
+                    {in_slot}
+                    onshapeclick={props.onshapeclick.clone()}
+                    onshapeenter={props.onshapeenter.clone()}
+                    onshapeleave={props.onshapeleave.clone()}
+                    onslotpress={props.onslotpress.clone()}
+                />
+            }
+        })
+        .collect();
+
+    let is_shape = element.tag.is_shape();
+    let onclick = if is_shape { shape_callback(&props.onshapeclick, &props.path) } else { None };
+    let onmouseenter = if is_shape { shape_callback(&props.onshapeenter, &props.path) } else { None };
+    let onmouseleave = if is_shape { shape_callback(&props.onshapeleave, &props.path) } else { None };
+    let pickable = slot.as_ref().is_some_and(|(_, disabled)| !disabled);
+    let onkeydown = props.onslotpress.clone().filter(|_| pickable).map(|onslotpress| {
+        let path = props.path.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if matches!(event.key().as_str(), "Enter" | " ") {
+                event.prevent_default();
+                // the page's Enter would open the slot highlighted before this one
+                event.stop_propagation();
+                onslotpress.emit((path.clone(), event.shift_key()));
+            }
+        })
+    });
+
+    let name = element.tag.name().to_string();
+    let mut vnode = html! {
+        <@{name} {onclick} {onmouseenter} {onmouseleave} {onkeydown}>{children}</@>
+    };
+    if let VNode::VTag(tag) = &mut vnode {
+        let mut attributes = Attributes::new();
+        let map = attributes.get_mut_index_map();
+        for property in &element.properties {
+            // an element written with both keeps its own `href`
+            if property.name() == "xlink:href" && element.property("href").is_some() {
+                continue;
+            }
+            map.insert(
+                AttrValue::from(property.dom_name().to_string()),
+                (AttrValue::from(unescape(property.value())), ApplyAttributeAs::Attribute),
+            );
+        }
+        if let (Tag::Svg, Some(view_box)) = (&element.tag, props.view_box) {
+            for (name, value) in [("viewBox", view_box.to_string()), ("width", "100%".to_string()), ("height", "100%".to_string())] {
+                map.insert(AttrValue::from(name), (AttrValue::from(value), ApplyAttributeAs::Attribute));
+            }
+        }
+        if is_shape && props.hovered.as_ref() == Some(&props.path) {
+            map.insert(
+                AttrValue::from(ShapeState::HOVER_ATTRIBUTE),
+                (AttrValue::from(""), ApplyAttributeAs::Attribute),
+            );
+        }
+        if let Some((slot, disabled)) = slot {
+            let mut aria = vec![("role", "button".to_string()), ("aria-label", slot)];
+            if disabled {
+                aria.push(("aria-disabled", "true".to_string()));
+            } else {
+                aria.push(("tabindex", "0".to_string()));
+            }
+            if is_highlighted(element) {
+                aria.push(("aria-current", "true".to_string()));
+            }
+            for (name, value) in aria {
+                map.insert(AttrValue::from(name), (AttrValue::from(value), ApplyAttributeAs::Attribute));
+            }
+        }
+        tag.set_attributes(attributes);
+    }
+    vnode
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PlanProps {
+    #[prop_or_default]
+    pub onshapeclick: Option<Callback<NodePath>>,
+    #[prop_or_default]
+    pub onshapeenter: Option<Callback<NodePath>>,
+    #[prop_or_default]
+    pub onshapeleave: Option<Callback<NodePath>>,
+    // element indices of the clicked shape and its ancestors, innermost first,
+    // in the numbering of `ElementKey`
+    #[prop_or_default]
+    pub onselect: Option<Callback<Vec<usize>>>,
+    // like `onselect`, for shift-clicks
+    #[prop_or_default]
+    pub onselectextend: Option<Callback<Vec<usize>>>,
+    #[prop_or_default]
+    pub view_box: Option<ViewBox>,
+}
+
+//LINK - Plan
+#[function_component(Plan)]
+pub fn plan(props: &PlanProps) -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let content = if let Some(svg) = &ctx.svg_content_highlighted.borrow().svg_content {
+        Some(svg.clone())
+    } else {
+        ctx.svg_content.borrow().svg_content.clone()
+    };
+    let svg = use_memo(content, |content| {
+        content.as_deref().and_then(|content| Svg::parse(content).ok()).map(Rc::new)
+    });
+    // read at click time, so the callback stays the same across highlights
+    let svg_ref = use_mut_ref(|| None::<Rc<Svg>>);
+    *svg_ref.borrow_mut() = (*svg).clone();
+    // a click carries no modifiers by the time it reaches a shape callback,
+    // so the press that started it records whether shift was held
+    let extend = use_mut_ref(|| false);
+    let onmousedown = {
+        let extend = extend.clone();
+        Callback::from(move |event: MouseEvent| *extend.borrow_mut() = event.shift_key())
+    };
+    let onselectclick = {
+        let svg_ref = svg_ref.clone();
+        let extend = extend.clone();
+        use_callback(
+            (props.onselect.clone(), props.onselectextend.clone()),
+            move |path: NodePath, (onselect, onselectextend)| {
+                let svg = svg_ref.borrow().clone();
+                let callback = if *extend.borrow() { onselectextend } else { onselect };
+                let (Some(callback), Some(svg)) = (callback, svg) else {
+                    return;
+                };
+                let indices = (1..=path.len())
+                    .rev()
+                    .filter_map(|len| element_index(&svg, &path[..len]))
+                    .collect();
+                callback.emit(indices);
+            },
+        )
+    };
+    let hovered = use_state(|| None::<(NodePath, ElementTag)>);
+    let onshapeenter = {
+        let svg_ref = svg_ref.clone();
+        let hovered = hovered.setter();
+        use_callback(props.onshapeenter.clone(), move |path: NodePath, onshapeenter| {
+            let svg = svg_ref.borrow().clone();
+            hovered.set(svg.and_then(|svg| slot_tag_at(&svg, &path)).map(|element_tag| (path.clone(), element_tag)));
+            if let Some(onshapeenter) = onshapeenter {
+                onshapeenter.emit(path);
+            }
+        })
+    };
+    let onshapeleave = {
+        let hovered = hovered.setter();
+        use_callback(props.onshapeleave.clone(), move |path: NodePath, onshapeleave| {
+            hovered.set(None);
+            if let Some(onshapeleave) = onshapeleave {
+                onshapeleave.emit(path);
+            }
+        })
+    };
+    // the tooltip follows the pointer without re-rendering the plan
+    let tooltip_ref = use_node_ref();
+    let pointer = use_mut_ref(|| (0i32, 0i32));
+    let onmousemove = {
+        let tooltip_ref = tooltip_ref.clone();
+        let pointer = pointer.clone();
+        Callback::from(move |event: MouseEvent| {
+            *pointer.borrow_mut() = (event.client_x(), event.client_y());
+            if let Some(tooltip) = tooltip_ref.cast::<HtmlElement>() {
+                let _ = tooltip.style().set_property("left", &format!("{}px", event.client_x() + 12));
+                let _ = tooltip.style().set_property("top", &format!("{}px", event.client_y() + 12));
+            }
+        })
+    };
+
+    let selectable = props.onselect.is_some() || props.onselectextend.is_some();
+    let onshapeclick = match (selectable, &props.onshapeclick) {
+        (false, onshapeclick) => onshapeclick.clone(),
+        (true, None) => Some(onselectclick),
+        (true, Some(onshapeclick)) => {
+            let onshapeclick = onshapeclick.clone();
+            Some(Callback::from(move |path: NodePath| {
+                onshapeclick.emit(path.clone());
+                onselectclick.emit(path);
+            }))
+        }
+    };
+    // a key press knows its modifiers, so it stands in for the mouse press
+    let onslotpress = use_callback(onshapeclick.clone(), move |(path, shift): (NodePath, bool), onshapeclick| {
+        *extend.borrow_mut() = shift;
+        if let Some(onshapeclick) = onshapeclick {
+            onshapeclick.emit(path);
+        }
+    });
+    let floor = Rc::new(ctx.floor(&ctx.current_floor()));
+    let label = plan_name(&floor, ctx.floor_slots().len());
+
+    let svg = match &*svg {
+        Some(svg) => svg.clone(),
+        None => return html! { <div>{"Loading..."}</div> },
+    };
+    let hovered_path = hovered.as_ref().map(|(path, _)| path.clone());
+    let roots: Html = svg
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| matches!(node, Node::Element(_)))
+        .map(|(index, _)| html! {
+            <PlanNode
+                svg={svg.clone()}
+                path={vec![index]}
+                hovered={hovered_path.clone().filter(|hovered| hovered.first() == Some(&index))}
+                view_box={props.view_box}
+                floor={floor.clone()}
+                onshapeclick={onshapeclick.clone()}
+                onshapeenter={onshapeenter.clone()}
+                onshapeleave={onshapeleave.clone()}
+                onslotpress={onslotpress.clone()}
+            />
+        })
+        .collect();
+
+    let (pointer_x, pointer_y) = *pointer.borrow();
+    html! {
+        <div
+            class="rnb-plan-canvas"
+            role="group"
+            aria-label={label}
+            style="width: 100%; height: 100%;"
+            {onmousedown}
+            {onmousemove}
+        >
+            {roots}
+            if let Some((_, element_tag)) = &*hovered {
+                <div
+                    class="rnb-tooltip"
+                    role="tooltip"
+                    ref={tooltip_ref}
+                    style={format!("position: fixed; pointer-events: none; left: {}px; top: {}px;", pointer_x + 12, pointer_y + 12)}
+                >
+                    <strong>{format!("Slot {}", element_tag.slot.clone().unwrap_or_default())}</strong>
+                    { for tooltip_rows(element_tag).into_iter().map(|(label, value)| html! {
+                        <div>{format!("{}: {}", label, value)}</div>
+                    }) }
+                </div>
+            }
+        </div>
+    }
+}
+
+fn tooltip_rows(element_tag: &ElementTag) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some(floor) = &element_tag.floor {
+        rows.push(("Floor", floor.clone()));
+    }
+    if let Some(building) = &element_tag.building {
+        rows.push(("Building", building.clone()));
+    }
+    if let Some(zone) = &element_tag.zone {
+        rows.push(("Zone", zone.clone()));
+    }
+    if let Some(kind) = &element_tag.kind {
+        rows.push(("Kind", kind.clone()));
+    }
+    if !element_tag.tags.is_empty() {
+        rows.push(("Tags", element_tag.tags.join(", ")));
+    }
+    rows
+}
@@ -0,0 +1,134 @@
+use gloo::events::EventListener;
+use gloo_utils::{document, window};
+use rnb_core::{floor::step, shortcut::cycle, Shortcut};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent};
+use yew::{function_component, html, use_context, use_effect_with, use_state, Callback, Html, MouseEvent};
+
+use crate::{EntityCase, EntityContext};
+
+// Keys typed into a form control belong to that control.
+pub fn is_typing(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .map(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "SELECT" | "TEXTAREA")
+                || element.dyn_ref::<HtmlElement>().is_some_and(|element| element.is_content_editable())
+        })
+        .unwrap_or(false)
+}
+
+// Tab only cycles slots while no control has focus.
+pub fn shortcut_of(event: &KeyboardEvent) -> Option<Shortcut> {
+    if is_typing(event) || event.ctrl_key() || event.meta_key() || event.alt_key() {
+        return None;
+    }
+    let nothing_focused = match (document().active_element(), document().body()) {
+        (Some(active), Some(body)) => active == *body,
+        (active, _) => active.is_none(),
+    };
+    Shortcut::from_key(&event.key(), event.shift_key(), nothing_focused)
+}
+
+fn focus(selector: &str) {
+    if let Some(element) = document()
+        .query_selector(selector)
+        .ok()
+        .flatten()
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+    {
+        let _ = element.focus();
+        element.scroll_into_view();
+    }
+}
+
+//LINK - Shortcuts
+// Listens for the keyboard commands on the whole page; zooming is left to the
+// viewport, which owns the view.
+#[function_component(Shortcuts)]
+pub fn shortcuts() -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let help = use_state(|| false);
+
+    {
+        let help = help.clone();
+        use_effect_with((ctx.clone(), *help), move |(ctx, showing)| {
+            let ctx = ctx.clone();
+            let showing = *showing;
+            let listener = EventListener::new(&window(), "keydown", move |event| {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else { return };
+                let Some(shortcut) = shortcut_of(event) else { return };
+                let slot = ctx.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+                let handled = match shortcut {
+                    Shortcut::FloorUp | Shortcut::FloorDown => {
+                        let offset = if shortcut == Shortcut::FloorUp { 1 } else { -1 };
+                        if let Some(floor) = step(&ctx.floors(), &ctx.current_floor(), offset) {
+                            ctx.dispatch(EntityCase::ProduceOption(Some(floor.token.clone())));
+                        }
+                        true
+                    }
+                    Shortcut::NextSlot | Shortcut::PreviousSlot => {
+                        let offset = if shortcut == Shortcut::NextSlot { 1 } else { -1 };
+                        if let Some(next) = cycle(&ctx.floor_slots(), slot.as_deref(), offset) {
+                            ctx.dispatch(EntityCase::Highlight(next.clone()));
+                        }
+                        true
+                    }
+                    Shortcut::OpenDetails if slot.is_some() => {
+                        focus(".rnb-details");
+                        true
+                    }
+                    Shortcut::Clear if showing => {
+                        help.set(false);
+                        true
+                    }
+                    Shortcut::Clear if slot.is_some() => {
+                        ctx.dispatch(EntityCase::ClearHighlight);
+                        true
+                    }
+                    Shortcut::FocusSearch => {
+                        focus(".rnb-search input");
+                        true
+                    }
+                    Shortcut::Help => {
+                        help.set(!showing);
+                        true
+                    }
+                    _ => false,
+                };
+                if handled {
+                    event.prevent_default();
+                }
+            });
+            move || drop(listener)
+        });
+    }
+
+    let ontoggle = {
+        let help = help.clone();
+        Callback::from(move |_: MouseEvent| help.set(!*help))
+    };
+
+    html! {
+        <>
+            <button class="rnb-shortcuts-toggle" onclick={ontoggle.clone()} title="Keyboard shortcuts (?)">{"?"}</button>
+            if *help {
+                <div class="rnb-shortcuts-help" style="position: fixed; inset: 0; display: flex; align-items: center; justify-content: center; background: rgba(0, 0, 0, 0.4); z-index: 10;">
+                    <div style="background: white; color: black; padding: 1em 1.5em; border-radius: 4px;">
+                        <h3>{"Keyboard shortcuts"}</h3>
+                        <table>
+                        { for Shortcut::ALL.iter().map(|shortcut| html! {
+                            <tr>
+                                <th scope="row"><kbd>{shortcut.keys()}</kbd></th>
+                                <td>{shortcut.description()}</td>
+                            </tr>
+                        }) }
+                        </table>
+                        <button onclick={ontoggle}>{"Close"}</button>
+                    </div>
+                </div>
+            }
+        </>
+    }
+}
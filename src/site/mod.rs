@@ -0,0 +1,115 @@
+use rnb_core::{site::{footprint, site_bounds, Footprint}, ShapeStyle};
+use yew::{
+    function_component, html, use_context, use_memo, use_state, Callback, Html, HtmlResult, MouseEvent, Properties,
+    Suspense,
+};
+
+use crate::{error::ErrorView, hooks, theme::{stored_preset, Theme}, Code};
+
+const PADDING: f64 = 0.05;
+
+#[derive(Properties, PartialEq)]
+pub struct SiteProps {
+    pub code: String,
+}
+
+//LINK - Site
+#[function_component(Site)]
+pub fn site(props: &SiteProps) -> Html {
+    let fallback = html! {<div>{"Loading..."}</div>};
+    html! {
+        <Suspense {fallback}>
+            <SitePlan code={props.code.clone()} />
+        </Suspense>
+    }
+}
+
+//LINK - SitePlan
+#[function_component(SitePlan)]
+fn site_plan(props: &SiteProps) -> HtmlResult {
+    // no entity supplies an override at site level, so the stored preset is used as is
+    let theme = use_context::<Theme>().unwrap_or_else(|| Theme::preset(&stored_preset()).unwrap_or_default());
+    let site = hooks::use_site(&props.code)?;
+    // index into the site's buildings; the same code may be placed twice
+    let selected = use_state(|| None::<usize>);
+    let hovered = use_state(|| None::<usize>);
+    let footprints = {
+        let site = site.result.clone().ok();
+        use_memo((props.code.clone(), site.is_some()), move |_| {
+            site.map(|site| site.buildings.iter().map(footprint).collect::<Vec<Footprint>>())
+                .unwrap_or_default()
+        })
+    };
+
+    let site = match site.result {
+        Ok(site) => site,
+        Err(error) => {
+            let onretry = site.retry.reform(|_: MouseEvent| ());
+            return Ok(html! { <ErrorView {error} {onretry} /> });
+        }
+    };
+    let view_box = site_bounds(&footprints).map(|bounds| bounds.padded(PADDING));
+    let base = theme.disabled.merged(&ShapeStyle::new(None, None, Some("1")));
+
+    Ok(html! {
+        <div class="rnb-site">
+            <h2>{site.name.clone()}</h2>
+            if let Some(view_box) = view_box {
+                <svg
+                    class="rnb-site-plan"
+                    viewBox={view_box.to_string()}
+                    width="100%"
+                    style={format!("height: 40vh; background: {};", theme.background)}
+                >
+                { for footprints.iter().enumerate().map(|(index, footprint)| {
+                    let style = if *selected == Some(index) {
+                        base.merged(&theme.highlight)
+                    } else if *hovered == Some(index) {
+                        base.merged(&theme.hover)
+                    } else {
+                        base.merged(&theme.focus)
+                    };
+                    let onclick = {
+                        let selected = selected.clone();
+                        Callback::from(move |_: MouseEvent| selected.set(Some(index)))
+                    };
+                    let onmouseenter = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |_: MouseEvent| hovered.set(Some(index)))
+                    };
+                    let onmouseleave = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |_: MouseEvent| hovered.set(None))
+                    };
+                    html! {
+                        <g key={index} class="rnb-site-building" data-code={footprint.code.clone()} style={style.css()} {onclick} {onmouseenter} {onmouseleave}>
+                            <title>{footprint.name.clone()}</title>
+                            { for footprint.rings.iter().map(|ring| if ring.closed {
+                                html! { <polygon points={ring.points_value()} /> }
+                            } else {
+                                html! { <polyline points={ring.points_value()} fill="none" /> }
+                            }) }
+                        </g>
+                    }
+                }) }
+                </svg>
+            }
+            <ul class="rnb-site-buildings">
+            { for footprints.iter().enumerate().map(|(index, footprint)| {
+                let onclick = {
+                    let selected = selected.clone();
+                    Callback::from(move |_: MouseEvent| selected.set(Some(index)))
+                };
+                html! {
+                    <li key={index}>
+                        <button {onclick} disabled={*selected == Some(index)}>{footprint.name.clone()}</button>
+                    </li>
+                }
+            }) }
+            </ul>
+            if let Some(footprint) = selected.and_then(|index| footprints.get(index)) {
+                <Code key={footprint.code.clone()} code={footprint.code.clone()} />
+            }
+        </div>
+    })
+}
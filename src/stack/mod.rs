@@ -0,0 +1,83 @@
+use rnb_core::{liquid::Svg, stack::{exploded, layer_bounds}, ShapeStyle, ViewBox};
+use yew::{function_component, html, use_context, use_memo, use_state, Callback, Html, MouseEvent, Properties};
+
+use crate::{theme::Theme, EntityContext};
+
+// vertical gap between storeys, as a share of the drawing's larger side
+const STOREY_SPACING: f64 = 0.3;
+const PADDING: f64 = 0.05;
+
+#[derive(Properties, PartialEq)]
+pub struct StackedViewProps {
+    // floor token picked to be shown in 2D
+    pub onfloor: Callback<String>,
+}
+
+//LINK - StackedView
+#[function_component(StackedView)]
+pub fn stacked_view(props: &StackedViewProps) -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let theme = use_context::<Theme>().unwrap_or_default();
+    let hovered = use_state(|| None::<String>);
+
+    let content = ctx.svg_content.borrow().svg_content.clone();
+    let floors: Vec<String> = ctx.floors().into_iter().map(|floor| floor.token).collect();
+    let layers = use_memo((content, floors), |(content, floors)| {
+        let Some(svg) = content.as_deref().and_then(|content| Svg::parse(content).ok()) else {
+            return Vec::new();
+        };
+        let spacing = ViewBox::of(&svg)
+            .map(|home| home.width.max(home.height) * STOREY_SPACING)
+            .unwrap_or_default();
+        exploded(&svg, floors, spacing)
+    });
+    let Some(view_box) = layer_bounds(&layers).map(|bounds| bounds.padded(PADDING)) else {
+        return html! { <div class="rnb-stack-empty">{"No floors to stack"}</div> };
+    };
+
+    let current = ctx.current_floor();
+    let base = theme.disabled.merged(&ShapeStyle::new(None, None, Some("1")));
+    html! {
+        <svg
+            class="rnb-stack"
+            viewBox={view_box.to_string()}
+            width="100%"
+            style={format!("height: 70vh; background: {};", theme.background)}
+        >
+        // bottom floor first, so upper floors are drawn over it
+        { for layers.iter().map(|layer| {
+            let style = if hovered.as_deref() == Some(layer.floor.as_str()) {
+                base.merged(&theme.hover)
+            } else if layer.floor == current {
+                base.merged(&theme.focus)
+            } else {
+                base.clone()
+            };
+            let onclick = {
+                let onfloor = props.onfloor.clone();
+                let floor = layer.floor.clone();
+                Callback::from(move |_: MouseEvent| onfloor.emit(floor.clone()))
+            };
+            let onmouseenter = {
+                let hovered = hovered.clone();
+                let floor = layer.floor.clone();
+                Callback::from(move |_: MouseEvent| hovered.set(Some(floor.clone())))
+            };
+            let onmouseleave = {
+                let hovered = hovered.clone();
+                Callback::from(move |_: MouseEvent| hovered.set(None))
+            };
+            html! {
+                <g key={layer.floor.clone()} class="rnb-stack-floor" data-floor={layer.floor.clone()} style={style.css()} {onclick} {onmouseenter} {onmouseleave}>
+                    <title>{ctx.floor(&layer.floor).label()}</title>
+                    { for layer.rings.iter().map(|ring| if ring.closed {
+                        html! { <polygon points={ring.points_value()} /> }
+                    } else {
+                        html! { <polyline points={ring.points_value()} fill="none" /> }
+                    }) }
+                </g>
+            }
+        }) }
+        </svg>
+    }
+}
@@ -1,12 +1,42 @@
-use serde::{Serialize, Deserialize};
-use yew::{Properties, Reducible};
-
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Properties)]
-pub struct Focus {
-    pub stroke: String,
-}
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Properties)]
-pub struct Unfocus {
-    pub stroke: String,
-    pub fill: String,
-}
\ No newline at end of file
+use web_sys::HtmlSelectElement;
+use yew::{function_component, html, Callback, Event, Html, Properties, TargetCast};
+
+pub use rnb_core::theme::{ShapeStyle, Theme, ThemeOverride};
+
+const STORAGE_KEY: &str = "theme";
+
+// The preset picked on an earlier visit, or the default one.
+pub fn stored_preset() -> String {
+    web_sys::window()
+        .and_then(|win| win.local_storage().ok())
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok())
+        .flatten()
+        .filter(|name| Theme::preset(name).is_some())
+        .unwrap_or(Theme::default().name)
+}
+
+pub fn store_preset(name: &str) {
+    if let Some(local_storage) = web_sys::window().and_then(|win| win.local_storage().ok()).flatten() {
+        let _ = local_storage.set_item(STORAGE_KEY, name);
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThemePickerProps {
+    pub value: String,
+    pub onchange: Callback<String>,
+}
+
+//LINK - ThemePicker
+#[function_component(ThemePicker)]
+pub fn theme_picker(props: &ThemePickerProps) -> Html {
+    let onchange = props.onchange.reform(|event: Event| event.target_unchecked_into::<HtmlSelectElement>().value());
+    html! {
+        <select class="rnb-theme-picker" {onchange}>
+        { for Theme::PRESETS.iter().map(|name| html! {
+            <option value={*name} selected={*name == props.value}>{*name}</option>
+        }) }
+        </select>
+    }
+}
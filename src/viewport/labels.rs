@@ -0,0 +1,67 @@
+use rnb_core::{label::{place, slot_labels}, liquid::Svg, ShapeStyle, ViewBox};
+use yew::{function_component, html, use_context, use_memo, Html, Properties};
+
+use crate::{theme::Theme, EntityContext};
+
+// on-screen text size, kept steady across zoom levels
+const FONT_PIXELS: f64 = 12.0;
+const HALO_PIXELS: f64 = 3.0;
+
+#[derive(Properties, PartialEq)]
+pub struct LabelLayerProps {
+    pub view_box: ViewBox,
+    // pixel size of the plan the layer sits over
+    pub client: (f64, f64),
+}
+
+//LINK - LabelLayer
+#[function_component(LabelLayer)]
+pub fn label_layer(props: &LabelLayerProps) -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let theme = use_context::<Theme>().unwrap_or_default();
+    let content = ctx.svg_content.borrow().svg_content.clone();
+    let labels = use_memo((content, ctx.current_floor()), |(content, floor)| {
+        content
+            .as_deref()
+            .and_then(|content| Svg::parse(content).ok())
+            .map(|svg| slot_labels(&svg, floor))
+            .unwrap_or_default()
+    });
+
+    let scale = props.view_box.units_per_pixel(props.client);
+    let font_size = FONT_PIXELS * scale;
+    let halo = theme
+        .label
+        .stroke_width
+        .as_deref()
+        .and_then(|width| width.trim_end_matches("px").trim().parse::<f64>().ok())
+        .unwrap_or(HALO_PIXELS);
+    // the halo is given in pixels, so it is rescaled like the text
+    let style = format!(
+        "{}; stroke-width: {}; paint-order: stroke;",
+        ShapeStyle { stroke_width: None, ..theme.label.clone() }.css(),
+        halo * scale
+    );
+
+    html! {
+        <svg
+            class="rnb-labels"
+            viewBox={props.view_box.to_string()}
+            width="100%"
+            height="100%"
+            style="position: absolute; inset: 0; pointer-events: none;"
+        >
+        { for place(&labels, font_size).into_iter().map(|label| html! {
+            <text
+                key={label.slot}
+                x={label.at.0.to_string()}
+                y={label.at.1.to_string()}
+                font-size={font_size.to_string()}
+                text-anchor="middle"
+                dominant-baseline="central"
+                style={style.clone()}
+            >{label.text}</text>
+        }) }
+        </svg>
+    }
+}
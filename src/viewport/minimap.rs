@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use rnb_core::ViewBox;
+use web_sys::Element;
+use yew::{function_component, html, use_context, use_mut_ref, use_node_ref, Callback, Html, MouseEvent, Properties};
+
+use crate::theme::{ShapeStyle, Theme};
+
+#[derive(Properties, PartialEq)]
+pub struct MinimapProps {
+    // the drawing's own viewBox, which the minimap always shows whole
+    pub home: ViewBox,
+    pub view_box: ViewBox,
+    pub outline: Rc<Vec<String>>,
+    // drawing coordinates the main view should be centred on
+    pub oncenter: Callback<(f64, f64)>,
+}
+
+//LINK - Minimap
+#[function_component(Minimap)]
+pub fn minimap(props: &MinimapProps) -> Html {
+    let theme = use_context::<Theme>().unwrap_or_default();
+    let minimap_ref = use_node_ref();
+    let dragging = use_mut_ref(|| false);
+
+    let center = {
+        let minimap_ref = minimap_ref.clone();
+        let home = props.home;
+        let oncenter = props.oncenter.clone();
+        move |event: &MouseEvent| {
+            let Some(minimap) = minimap_ref.cast::<Element>() else { return };
+            let rect = minimap.get_bounding_client_rect();
+            let point = (event.client_x() as f64 - rect.left(), event.client_y() as f64 - rect.top());
+            oncenter.emit(home.client_to_view(point, (rect.width().max(1.0), rect.height().max(1.0))));
+        }
+    };
+    let onmousedown = {
+        let dragging = dragging.clone();
+        let center = center.clone();
+        Callback::from(move |event: MouseEvent| {
+            *dragging.borrow_mut() = true;
+            center(&event);
+        })
+    };
+    let onmousemove = {
+        let dragging = dragging.clone();
+        Callback::from(move |event: MouseEvent| {
+            if *dragging.borrow() {
+                center(&event);
+            }
+        })
+    };
+    let onmouseup = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: MouseEvent| *dragging.borrow_mut() = false)
+    };
+
+    let view_box = props.view_box;
+    let shape_style = theme.disabled.css();
+    let view_style = theme.highlight.merged(&ShapeStyle::new(None, Some("none"), Some("2"))).css();
+    html! {
+        <svg
+            class="rnb-minimap"
+            ref={minimap_ref}
+            viewBox={props.home.to_string()}
+            style={format!("width: 160px; height: 120px; background: {}; border: 1px solid #999; cursor: crosshair;", theme.background)}
+            {onmousedown}
+            {onmousemove}
+            onmouseleave={onmouseup.clone()}
+            {onmouseup}
+        >
+            { for props.outline.iter().map(|points| html! {
+                <polygon points={points.clone()} style={shape_style.clone()} vector-effect="non-scaling-stroke" />
+            }) }
+            <rect
+                x={view_box.x.to_string()}
+                y={view_box.y.to_string()}
+                width={view_box.width.to_string()}
+                height={view_box.height.to_string()}
+                style={view_style}
+                vector-effect="non-scaling-stroke"
+            />
+        </svg>
+    }
+}
@@ -0,0 +1,354 @@
+use std::{cell::Cell, rc::Rc};
+
+use gloo::events::{EventListener, EventListenerOptions, EventListenerPhase};
+use gloo_utils::window;
+use rnb_core::{liquid::Svg, viewport::{bounds, outline, ViewBox}, Shortcut};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, TouchEvent, WheelEvent};
+use yew::{
+    function_component, html, use_context, use_effect_with, use_memo, use_node_ref, use_reducer, Callback, Html,
+    MouseEvent, Properties, Reducible,
+};
+
+use crate::{plan, shortcuts, theme::Theme, EntityContext};
+
+pub mod labels;
+pub mod minimap;
+
+const ZOOM_STEP: f64 = 1.25;
+// limits relative to the drawing's own viewBox
+const MAX_ZOOM: f64 = 20.0;
+const MIN_ZOOM: f64 = 0.25;
+const FLOOR_PADDING: f64 = 0.05;
+const SELECTION_PADDING: f64 = 0.25;
+// pointer travel in pixels after which a press counts as a drag, not a click
+const DRAG_THRESHOLD: f64 = 3.0;
+
+//LINK - View Reducible
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct View {
+    pub home: Option<ViewBox>,
+    pub view_box: Option<ViewBox>,
+}
+// Pointer positions are in pixels relative to the viewport element, `client`
+// is that element's size.
+pub enum ViewAction {
+    Reset(Option<ViewBox>),
+    Fit(ViewBox),
+    Zoom { factor: f64, at: (f64, f64), client: (f64, f64) },
+    ZoomCenter(f64),
+    // drawing coordinates
+    Center((f64, f64)),
+    Pan { dx: f64, dy: f64, client: (f64, f64) },
+}
+impl View {
+    fn zoomed(&self, view_box: &ViewBox, factor: f64, at: (f64, f64)) -> ViewBox {
+        let factor = match self.home {
+            Some(home) => {
+                let zoom = home.width / view_box.width * factor;
+                factor * zoom.clamp(MIN_ZOOM, MAX_ZOOM) / zoom
+            },
+            None => factor,
+        };
+        view_box.zoom(factor, at)
+    }
+}
+impl Reducible for View {
+    type Action = ViewAction;
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let view_box = match (action, self.view_box) {
+            (ViewAction::Reset(home), _) => return View { home, view_box: home }.into(),
+            (ViewAction::Fit(view_box), _) => view_box,
+            (ViewAction::Zoom { factor, at, client }, Some(view_box)) => {
+                let at = view_box.client_to_view(at, client);
+                self.zoomed(&view_box, factor, at)
+            },
+            (ViewAction::ZoomCenter(factor), Some(view_box)) => self.zoomed(&view_box, factor, view_box.center()),
+            (ViewAction::Center(point), Some(view_box)) => view_box.centered_on(point),
+            (ViewAction::Pan { dx, dy, client }, Some(view_box)) => {
+                let scale = view_box.units_per_pixel(client);
+                view_box.pan(-dx * scale, -dy * scale)
+            },
+            (_, None) => return self,
+        };
+        View { home: self.home, view_box: Some(view_box) }.into()
+    }
+}
+
+fn client_size(element: &Element) -> (f64, f64) {
+    (element.client_width().max(1) as f64, element.client_height().max(1) as f64)
+}
+
+fn relative(element: &Element, client_x: i32, client_y: i32) -> (f64, f64) {
+    let rect = element.get_bounding_client_rect();
+    (client_x as f64 - rect.left(), client_y as f64 - rect.top())
+}
+
+fn touch_points(element: &Element, event: &TouchEvent) -> Vec<(f64, f64)> {
+    let touches = event.touches();
+    (0..touches.length())
+        .filter_map(|index| touches.get(index))
+        .map(|touch| relative(element, touch.client_x(), touch.client_y()))
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ViewportProps {
+    #[prop_or_default]
+    pub onselect: Option<Callback<Vec<usize>>>,
+    #[prop_or_default]
+    pub onselectextend: Option<Callback<Vec<usize>>>,
+}
+
+//LINK - Viewport
+#[function_component(Viewport)]
+pub fn viewport(props: &ViewportProps) -> Html {
+    let ctx = use_context::<EntityContext>().expect("no Svg Content ctx found");
+    let theme = use_context::<Theme>().unwrap_or_default();
+    let view = use_reducer(View::default);
+    let container_ref = use_node_ref();
+
+    let content = ctx.svg_content.borrow().svg_content.clone();
+    let svg = use_memo(content.clone(), |content| {
+        content.as_deref().and_then(|content| Svg::parse(content).ok())
+    });
+    let floor = ctx.current_floor();
+    let floor_outline = {
+        let svg = svg.clone();
+        use_memo((content.clone(), floor.clone()), move |(_, floor)| {
+            (*svg)
+                .as_ref()
+                .map(|svg| outline(svg, |element_tag| element_tag.floor_token().as_deref() == Some(floor.as_str())))
+                .unwrap_or_default()
+        })
+    };
+    let slot = ctx.current_option.borrow().x.clone().filter(|slot| !slot.is_empty());
+
+    let home = (*svg).as_ref().and_then(ViewBox::of);
+    let floor_bounds = {
+        let svg = svg.clone();
+        *use_memo((content.clone(), floor.clone()), move |(_, floor)| {
+            (*svg)
+                .as_ref()
+                .and_then(|svg| bounds(svg, |element_tag| element_tag.floor_token().as_deref() == Some(floor.as_str())))
+                .map(|floor_bounds| floor_bounds.padded(FLOOR_PADDING))
+                .or(home)
+        })
+    };
+    let slot_bounds = {
+        let svg = svg.clone();
+        *use_memo((content, floor.clone(), slot.clone()), move |(_, floor, slot)| {
+            slot.as_ref().and_then(|slot| {
+                (*svg).as_ref().and_then(|svg| {
+                    bounds(svg, |element_tag| {
+                        element_tag.slot_token().as_ref() == Some(slot) && element_tag.floor_token().as_deref() == Some(floor.as_str())
+                    })
+                })
+            })
+        })
+    };
+
+    {
+        let view = view.dispatcher();
+        use_effect_with(home, move |home| view.dispatch(ViewAction::Reset(*home)));
+    }
+    {
+        let view = view.dispatcher();
+        use_effect_with((floor.clone(), floor_bounds), move |(_, floor_bounds)| {
+            if let Some(floor_bounds) = floor_bounds {
+                view.dispatch(ViewAction::Fit(*floor_bounds));
+            }
+        });
+    }
+    {
+        let view = view.dispatcher();
+        use_effect_with((slot.clone(), slot_bounds), move |(_, slot_bounds)| {
+            if let Some(slot_bounds) = slot_bounds {
+                view.dispatch(ViewAction::Fit(slot_bounds.padded(SELECTION_PADDING)));
+            }
+        });
+    }
+
+    // Wheel and touch listeners must be able to prevent scrolling, which Yew's
+    // delegated listeners cannot, and a drag has to follow the pointer outside
+    // the plan. All of them are dropped on unmount.
+    {
+        let view = view.dispatcher();
+        let container_ref = container_ref.clone();
+        use_effect_with((), move |_| {
+            let mut listeners: Vec<EventListener> = Vec::new();
+            if let Some(container) = container_ref.cast::<HtmlElement>() {
+                let active = EventListenerOptions { phase: EventListenerPhase::Bubble, passive: false };
+                let drag_from = Rc::new(Cell::new(None::<(f64, f64)>));
+                let dragged = Rc::new(Cell::new(0.0f64));
+                let pinch = Rc::new(Cell::new(None::<f64>));
+
+                listeners.push({
+                    let (view, element) = (view.clone(), container.clone());
+                    EventListener::new_with_options(&container, "wheel", active, move |event| {
+                        let Some(event) = event.dyn_ref::<WheelEvent>() else { return };
+                        event.prevent_default();
+                        view.dispatch(ViewAction::Zoom {
+                            factor: (-event.delta_y() * 0.002).exp(),
+                            at: relative(&element, event.client_x(), event.client_y()),
+                            client: client_size(&element),
+                        });
+                    })
+                });
+                listeners.push({
+                    let (drag_from, dragged, element) = (drag_from.clone(), dragged.clone(), container.clone());
+                    EventListener::new(&container, "mousedown", move |event| {
+                        let Some(event) = event.dyn_ref::<MouseEvent>() else { return };
+                        if event.button() == 0 {
+                            drag_from.set(Some(relative(&element, event.client_x(), event.client_y())));
+                            dragged.set(0.0);
+                        }
+                    })
+                });
+                listeners.push({
+                    let (view, drag_from, dragged, element) = (view.clone(), drag_from.clone(), dragged.clone(), container.clone());
+                    EventListener::new(&window(), "mousemove", move |event| {
+                        let (Some(event), Some((from_x, from_y))) = (event.dyn_ref::<MouseEvent>(), drag_from.get()) else { return };
+                        let (x, y) = relative(&element, event.client_x(), event.client_y());
+                        dragged.set(dragged.get() + (x - from_x).abs() + (y - from_y).abs());
+                        drag_from.set(Some((x, y)));
+                        view.dispatch(ViewAction::Pan { dx: x - from_x, dy: y - from_y, client: client_size(&element) });
+                    })
+                });
+                listeners.push({
+                    let drag_from = drag_from.clone();
+                    EventListener::new(&window(), "mouseup", move |_| drag_from.set(None))
+                });
+                listeners.push({
+                    let view = view.clone();
+                    EventListener::new(&window(), "keydown", move |event| {
+                        let Some(event) = event.dyn_ref::<KeyboardEvent>() else { return };
+                        let factor = match shortcuts::shortcut_of(event) {
+                            Some(Shortcut::ZoomIn) => ZOOM_STEP,
+                            Some(Shortcut::ZoomOut) => 1.0 / ZOOM_STEP,
+                            _ => return,
+                        };
+                        event.prevent_default();
+                        view.dispatch(ViewAction::ZoomCenter(factor));
+                    })
+                });
+                // a drag that ends over a room must not select it
+                listeners.push({
+                    let dragged = dragged.clone();
+                    let capture = EventListenerOptions { phase: EventListenerPhase::Capture, passive: false };
+                    EventListener::new_with_options(&container, "click", capture, move |event| {
+                        if dragged.get() > DRAG_THRESHOLD {
+                            event.stop_propagation();
+                        }
+                        dragged.set(0.0);
+                    })
+                });
+                listeners.push({
+                    let (drag_from, pinch, element) = (drag_from.clone(), pinch.clone(), container.clone());
+                    EventListener::new_with_options(&container, "touchstart", active, move |event| {
+                        let Some(event) = event.dyn_ref::<TouchEvent>() else { return };
+                        match touch_points(&element, event)[..] {
+                            [point] => {
+                                drag_from.set(Some(point));
+                                pinch.set(None);
+                            },
+                            [a, b] => {
+                                event.prevent_default();
+                                drag_from.set(None);
+                                pinch.set(Some((a.0 - b.0).hypot(a.1 - b.1)));
+                            },
+                            _ => {},
+                        }
+                    })
+                });
+                listeners.push({
+                    let (view, drag_from, dragged, pinch, element) = (view.clone(), drag_from.clone(), dragged.clone(), pinch.clone(), container.clone());
+                    EventListener::new_with_options(&container, "touchmove", active, move |event| {
+                        let Some(event) = event.dyn_ref::<TouchEvent>() else { return };
+                        event.prevent_default();
+                        let client = client_size(&element);
+                        let points = touch_points(&element, event);
+                        match (points.as_slice(), drag_from.get(), pinch.get()) {
+                            ([point], Some((from_x, from_y)), _) => {
+                                let (x, y) = *point;
+                                dragged.set(dragged.get() + (x - from_x).abs() + (y - from_y).abs());
+                                drag_from.set(Some((x, y)));
+                                view.dispatch(ViewAction::Pan { dx: x - from_x, dy: y - from_y, client });
+                            },
+                            ([a, b], _, Some(distance)) if distance > 0.0 => {
+                                let (a, b) = (*a, *b);
+                                let next = (a.0 - b.0).hypot(a.1 - b.1);
+                                pinch.set(Some(next));
+                                view.dispatch(ViewAction::Zoom {
+                                    factor: next / distance,
+                                    at: ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0),
+                                    client,
+                                });
+                            },
+                            _ => {},
+                        }
+                    })
+                });
+                listeners.push({
+                    let (drag_from, pinch) = (drag_from.clone(), pinch.clone());
+                    EventListener::new(&container, "touchend", move |_| {
+                        drag_from.set(None);
+                        pinch.set(None);
+                    })
+                });
+            }
+            move || drop(listeners)
+        });
+    }
+
+    let onzoomin = {
+        let view = view.dispatcher();
+        Callback::from(move |_: MouseEvent| view.dispatch(ViewAction::ZoomCenter(ZOOM_STEP)))
+    };
+    let onzoomout = {
+        let view = view.dispatcher();
+        Callback::from(move |_: MouseEvent| view.dispatch(ViewAction::ZoomCenter(1.0 / ZOOM_STEP)))
+    };
+    let oncenter = {
+        let view = view.dispatcher();
+        Callback::from(move |point: (f64, f64)| view.dispatch(ViewAction::Center(point)))
+    };
+    let onfit = {
+        let view = view.dispatcher();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(floor_bounds) = floor_bounds {
+                view.dispatch(ViewAction::Fit(floor_bounds));
+            }
+        })
+    };
+
+    // unknown until the container has rendered once
+    let client = container_ref.cast::<Element>().map(|element| client_size(&element));
+
+    html! {
+        <div class="rnb-viewport">
+            <div class="rnb-viewport-controls">
+                <button onclick={onzoomin} title="Zoom in">{"+"}</button>
+                <button onclick={onzoomout} title="Zoom out">{"−"}</button>
+                <button onclick={onfit} title="Fit floor">{"Fit"}</button>
+            </div>
+            <div
+                class="rnb-plan"
+                ref={container_ref}
+                style={format!("position: relative; overflow: hidden; height: 70vh; touch-action: none; background: {};", theme.background)}
+            >
+                <plan::Plan
+                    onselect={props.onselect.clone()}
+                    onselectextend={props.onselectextend.clone()}
+                    view_box={view.view_box}
+                />
+                if let (true, Some(view_box), Some(client)) = (ctx.show_labels, view.view_box, client) {
+                    <labels::LabelLayer {view_box} {client} />
+                }
+            </div>
+            if let (Some(home), Some(view_box)) = (view.home, view.view_box) {
+                <minimap::Minimap {home} {view_box} outline={floor_outline} {oncenter} />
+            }
+        </div>
+    }
+}